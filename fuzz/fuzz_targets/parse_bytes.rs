@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use replay_reader::parser::parse_bytes_safe;
+
+fuzz_target!(|data: &[u8]| {
+  let _ = parse_bytes_safe(data);
+});
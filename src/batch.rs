@@ -0,0 +1,54 @@
+use crate::parser::{ParseError, Parser, ReplaySummary};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+// Parses every .replay file in the directory on all available cores, results are sorted by path.
+// Each file gets its own parser, a file failing to parse doesn't affect the others
+pub fn parse_dir<P: AsRef<Path>>(path: P) -> io::Result<Vec<(PathBuf, Result<ReplaySummary, ParseError>)>> {
+  let mut paths: Vec<PathBuf> = vec![];
+
+  for entry in fs::read_dir(path)? {
+    let entry_path = entry?.path();
+    if entry_path.is_file() && entry_path.extension().is_some_and(|extension| extension == "replay") {
+      paths.push(entry_path);
+    }
+  }
+
+  paths.sort();
+
+  return Ok(parse_files(paths));
+}
+
+pub fn parse_files(paths: Vec<PathBuf>) -> Vec<(PathBuf, Result<ReplaySummary, ParseError>)> {
+  let thread_count = thread::available_parallelism().map_or(1, |count| count.get()).min(paths.len());
+  let next_index = AtomicUsize::new(0);
+  let results: Mutex<Vec<Option<Result<ReplaySummary, ParseError>>>> = Mutex::new(paths.iter().map(|_| None).collect());
+
+  thread::scope(|scope| {
+    for _ in 0..thread_count {
+      scope.spawn(|| loop {
+        let index = next_index.fetch_add(1, Ordering::Relaxed);
+        if index >= paths.len() {
+          break;
+        }
+
+        let result = parse_file(&paths[index]);
+        results.lock().unwrap()[index] = Some(result);
+      });
+    }
+  });
+
+  let results = results.into_inner().unwrap();
+
+  return paths.into_iter().zip(results).map(|(path, result)| (path, result.unwrap())).collect();
+}
+
+fn parse_file(path: &Path) -> Result<ReplaySummary, ParseError> {
+  let buffer = fs::read(path)?;
+
+  return Parser::from_bytes(buffer).parse_to_summary();
+}
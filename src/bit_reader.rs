@@ -0,0 +1,165 @@
+use crate::reader::ReaderError;
+
+// Reads the bit streams of the netcode in replay data chunks and checkpoints.
+// Bits are consumed least significant first within each byte, like the engine's FBitReader
+pub struct BitReader<'a> {
+  buffer: &'a [u8],
+  bit_count: usize,
+  pub position: usize
+}
+
+impl<'a> BitReader<'a> {
+  pub fn new(buffer: &'a [u8]) -> Self {
+    return Self {
+      buffer,
+      bit_count: buffer.len() * 8,
+      position: 0
+    }
+  }
+
+  // Packets store their length in bits, everything after bit_count is padding
+  pub fn with_bit_count(buffer: &'a [u8], bit_count: usize) -> Self {
+    return Self {
+      buffer,
+      bit_count: bit_count.min(buffer.len() * 8),
+      position: 0
+    }
+  }
+
+  pub fn bit_count(&self) -> usize {
+    return self.bit_count;
+  }
+
+  pub fn remaining_bits(&self) -> usize {
+    return self.bit_count - self.position;
+  }
+
+  pub fn is_eof(&self) -> bool {
+    return self.position >= self.bit_count;
+  }
+
+  fn check_bits(&self, count: usize) -> Result<(), ReaderError> {
+    if count > self.remaining_bits() {
+      return Err(ReaderError::OutOfBounds {
+        offset: self.position / 8,
+        length: count.div_ceil(8),
+        buffer_len: self.buffer.len()
+      });
+    }
+
+    return Ok(());
+  }
+
+  pub fn skip_bits(&mut self, count: usize) -> Result<(), ReaderError> {
+    self.check_bits(count)?;
+    self.position += count;
+    return Ok(());
+  }
+
+  pub fn read_bit(&mut self) -> Result<bool, ReaderError> {
+    self.check_bits(1)?;
+    let bit = self.buffer[self.position / 8] >> (self.position % 8) & 1;
+    self.position += 1;
+    return Ok(bit == 1);
+  }
+
+  // Up to 64 bits as a number, the first bit read is the lowest one
+  pub fn read_bits_u64(&mut self, count: usize) -> Result<u64, ReaderError> {
+    self.check_bits(count)?;
+    if count > 64 {
+      return Err(ReaderError::InvalidBitCount { count, max: 64 });
+    }
+
+    let mut value: u64 = 0;
+    for i in 0..count {
+      if self.read_bit()? {
+        value |= 1 << i;
+      }
+    }
+
+    return Ok(value);
+  }
+
+  pub fn read_bits_u32(&mut self, count: usize) -> Result<u32, ReaderError> {
+    if count > 32 {
+      return Err(ReaderError::InvalidBitCount { count, max: 32 });
+    }
+
+    return Ok(self.read_bits_u64(count)? as u32);
+  }
+
+  // Any number of bits packed into bytes the same way they were read, the last byte is zero filled
+  pub fn read_bits(&mut self, count: usize) -> Result<Vec<u8>, ReaderError> {
+    self.check_bits(count)?;
+
+    let mut bytes = vec![0u8; count.div_ceil(8)];
+    for i in 0..count {
+      if self.read_bit()? {
+        bytes[i / 8] |= 1 << (i % 8);
+      }
+    }
+
+    return Ok(bytes);
+  }
+
+  pub fn read_byte(&mut self) -> Result<u8, ReaderError> {
+    return Ok(self.read_bits_u64(8)? as u8);
+  }
+
+  pub fn read_bytes(&mut self, count: usize) -> Result<Vec<u8>, ReaderError> {
+    return self.read_bits(count.saturating_mul(8));
+  }
+
+  pub fn read_u16(&mut self) -> Result<u16, ReaderError> {
+    return Ok(self.read_bits_u64(16)? as u16);
+  }
+
+  pub fn read_u32(&mut self) -> Result<u32, ReaderError> {
+    return Ok(self.read_bits_u64(32)? as u32);
+  }
+
+  pub fn read_u64(&mut self) -> Result<u64, ReaderError> {
+    return self.read_bits_u64(64);
+  }
+
+  pub fn read_i32(&mut self) -> Result<i32, ReaderError> {
+    return Ok(self.read_u32()? as i32);
+  }
+
+  pub fn read_f32(&mut self) -> Result<f32, ReaderError> {
+    return Ok(f32::from_bits(self.read_u32()?));
+  }
+
+  // SerializeInt: a value below max, only reading as many bits as max needs
+  pub fn read_serialized_int(&mut self, max: u32) -> Result<u32, ReaderError> {
+    let mut value: u32 = 0;
+    let mut mask: u32 = 1;
+
+    while mask != 0 && value.saturating_add(mask) < max {
+      if self.read_bit()? {
+        value |= mask;
+      }
+
+      mask = mask.wrapping_shl(1);
+    }
+
+    return Ok(value);
+  }
+
+  // SerializeIntPacked: bytes holding 7 bits of the value each, the lowest bit says whether another byte follows
+  pub fn read_int_packed(&mut self) -> Result<u32, ReaderError> {
+    let start_position = self.position;
+    let mut value: u32 = 0;
+
+    for i in 0..5 {
+      let byte = self.read_byte()?;
+      value |= ((byte >> 1) as u32).wrapping_shl(7 * i);
+
+      if byte & 1 == 0 {
+        return Ok(value);
+      }
+    }
+
+    return Err(ReaderError::InvalidPackedInt { offset: start_position / 8 });
+  }
+}
@@ -0,0 +1,132 @@
+use crate::compression::Decompressor;
+use crate::parser::{EventFilter, EventHandler, EventInfo, ParseError, ParseMode, Parser, ProgressCallback};
+use crate::reader::{CipherMode, ReadSeek, Reader};
+
+/// Collects the parser options so they can be applied to a parser for any kind of source.
+/// Parser::new and Parser::from_bytes stay the simple defaults
+///
+/// ```no_run
+/// use replay_reader::builder::ParserBuilder;
+///
+/// let buffer = std::fs::read("1.replay").unwrap();
+/// let mut parser = ParserBuilder::new()
+///   .collect_raw_events(true)
+///   .lossy_strings(true)
+///   .build_from_bytes(buffer)
+///   .unwrap();
+///
+/// parser.parse().unwrap();
+/// println!("{} raw events", parser.raw_events().len());
+/// ```
+#[derive(Default)]
+pub struct ParserBuilder {
+  encryption_key: Option<Vec<u8>>,
+  collect_raw_events: bool,
+  decrypt_raw_events: bool,
+  meta_only: bool,
+  parse_mode: ParseMode,
+  lossy_strings: bool,
+  cipher_mode: CipherMode,
+  decompressor: Option<Box<dyn Decompressor>>,
+  event_handlers: Vec<(String, EventHandler)>,
+  event_filter: Option<EventFilter>,
+  progress: Option<ProgressCallback>
+}
+
+impl ParserBuilder {
+  pub fn new() -> Self {
+    return Self {
+      decrypt_raw_events: true,
+      ..Default::default()
+    };
+  }
+
+  // Checked when the parser is built, AES-256 keys have to be 32 bytes long
+  pub fn encryption_key(mut self, key: Vec<u8>) -> Self {
+    self.encryption_key = Some(key);
+    return self;
+  }
+
+  pub fn collect_raw_events(mut self, collect: bool) -> Self {
+    self.collect_raw_events = collect;
+    return self;
+  }
+
+  // Only used together with collect_raw_events
+  pub fn decrypt_raw_events(mut self, decrypt: bool) -> Self {
+    self.decrypt_raw_events = decrypt;
+    return self;
+  }
+
+  pub fn meta_only(mut self, meta_only: bool) -> Self {
+    self.meta_only = meta_only;
+    return self;
+  }
+
+  pub fn parse_mode(mut self, parse_mode: ParseMode) -> Self {
+    self.parse_mode = parse_mode;
+    return self;
+  }
+
+  pub fn lossy_strings(mut self, lossy: bool) -> Self {
+    self.lossy_strings = lossy;
+    return self;
+  }
+
+  pub fn cipher_mode(mut self, cipher_mode: CipherMode) -> Self {
+    self.cipher_mode = cipher_mode;
+    return self;
+  }
+
+  pub fn decompressor(mut self, decompressor: impl Decompressor + 'static) -> Self {
+    self.decompressor = Some(Box::new(decompressor));
+    return self;
+  }
+
+  pub fn on_event<F>(mut self, key: &str, handler: F) -> Self where F: FnMut(&EventInfo, &mut Reader) -> bool + Send + 'static {
+    self.event_handlers.push((String::from(key), Box::new(handler)));
+    return self;
+  }
+
+  pub fn event_filter<F>(mut self, filter: F) -> Self where F: Fn(&EventInfo) -> bool + Send + 'static {
+    self.event_filter = Some(Box::new(filter));
+    return self;
+  }
+
+  pub fn progress<F>(mut self, callback: F) -> Self where F: FnMut(usize, usize) + Send + 'static {
+    self.progress = Some(Box::new(callback));
+    return self;
+  }
+
+  #[cfg(feature = "std")]
+  pub fn build_from_path(self, path: &str) -> Result<Parser, ParseError> {
+    return self.apply(Parser::new(path)?);
+  }
+
+  pub fn build_from_bytes(self, buffer: Vec<u8>) -> Result<Parser, ParseError> {
+    return self.apply(Parser::from_bytes(buffer));
+  }
+
+  pub fn build_from_stream<R: ReadSeek + 'static>(self, source: R) -> Result<Parser, ParseError> {
+    return self.apply(Parser::from_stream(source)?);
+  }
+
+  fn apply(self, mut parser: Parser) -> Result<Parser, ParseError> {
+    if let Some(key) = self.encryption_key {
+      parser = parser.with_encryption_key(key)?;
+    }
+
+    parser.reader.lossy_strings = self.lossy_strings;
+    parser.reader.cipher_mode = self.cipher_mode;
+    parser.collect_raw_events = self.collect_raw_events;
+    parser.decrypt_raw_events = self.decrypt_raw_events;
+    parser.meta_only = self.meta_only;
+    parser.parse_mode = self.parse_mode;
+    parser.decompressor = self.decompressor;
+    parser.event_handlers = self.event_handlers;
+    parser.event_filter = self.event_filter;
+    parser.progress = self.progress;
+
+    return Ok(parser);
+  }
+}
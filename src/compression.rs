@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub struct DecompressError {
+  pub message: String
+}
+
+impl DecompressError {
+  pub fn new(message: &str) -> Self {
+    return Self {
+      message: String::from(message)
+    }
+  }
+}
+
+impl fmt::Display for DecompressError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    return write!(f, "decompression failed: {}", self.message);
+  }
+}
+
+impl Error for DecompressError {}
+
+// Compressed replays store every data and checkpoint payload as
+// [decompressed size: i32][compressed size: i32][compressed bytes].
+// Fortnite uses Oodle for these, which can't be shipped with this crate, so callers plug their own in
+pub trait Decompressor: Send + Sync {
+  fn decompress(&self, data: &[u8], decompressed_size: usize) -> Result<Vec<u8>, DecompressError>;
+}
+
+#[cfg(feature = "zlib")]
+#[derive(Debug)]
+pub struct ZlibDecompressor;
+
+#[cfg(feature = "zlib")]
+impl Decompressor for ZlibDecompressor {
+  fn decompress(&self, data: &[u8], decompressed_size: usize) -> Result<Vec<u8>, DecompressError> {
+    use flate2::read::ZlibDecoder;
+    use std::io::Read;
+
+    // The sizes come from the replay, so neither the allocation nor the output is allowed to grow past what the data can hold
+    let mut decompressed = Vec::with_capacity(decompressed_size.min(data.len().saturating_mul(16)));
+    ZlibDecoder::new(data).take(decompressed_size as u64 + 1).read_to_end(&mut decompressed).map_err(|err| DecompressError::new(&err.to_string()))?;
+
+    if decompressed.len() != decompressed_size {
+      return Err(DecompressError::new(&format!("expected {} bytes but got {}", decompressed_size, decompressed.len())));
+    }
+
+    return Ok(decompressed);
+  }
+}
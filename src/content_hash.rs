@@ -0,0 +1,122 @@
+use crate::parser::{Elimination, Header, MatchStats, Meta, Player, Replay, TeamMatchStats};
+use sha2::{Digest, Sha256};
+
+// Feeds the fields in a fixed order, strings and lists are length prefixed so neighbouring fields can't run into
+// each other
+struct ContentHasher {
+  hasher: Sha256
+}
+
+impl ContentHasher {
+  fn u32(&mut self, value: u32) {
+    self.hasher.update(value.to_le_bytes());
+  }
+
+  fn f32(&mut self, value: f32) {
+    self.u32(value.to_bits());
+  }
+
+  fn bool(&mut self, value: bool) {
+    self.hasher.update([value as u8]);
+  }
+
+  fn string(&mut self, value: &str) {
+    self.u32(value.len() as u32);
+    self.hasher.update(value.as_bytes());
+  }
+
+  fn option<T>(&mut self, value: Option<T>, mut write: impl FnMut(&mut Self, T)) {
+    self.bool(value.is_some());
+    if let Some(value) = value {
+      write(self, value);
+    }
+  }
+
+  // file version, length, network version and changelist. The name can be changed in game and the timestamp,
+  // live flag, compression and encryption say nothing about the match
+  fn meta(&mut self, meta: &Meta) {
+    self.u32(meta.file_version);
+    self.u32(meta.length_in_ms);
+    self.u32(meta.network_version);
+    self.u32(meta.changelist);
+  }
+
+  // Everything but the magic
+  fn header(&mut self, header: &Header) {
+    self.u32(header.network_version);
+    self.u32(header.network_checksum);
+    self.u32(header.engine_network_version);
+    self.u32(header.game_network_protocol);
+    self.option(header.id.as_deref(), |hasher, id| hasher.string(id));
+    self.string(&header.version.branch);
+    self.u32(header.version.patch as u32);
+    self.u32(header.version.changelist);
+
+    self.u32(header.level_names_and_times.len() as u32);
+    for (level_name, time) in header.level_names_and_times.iter() {
+      self.string(level_name);
+      self.u32(*time);
+    }
+
+    self.u32(header.flags);
+    self.u32(header.game_specific_data.len() as u32);
+    for data in header.game_specific_data.iter() {
+      self.string(data);
+    }
+  }
+
+  fn player(&mut self, player: &Player) {
+    self.string(player.id.as_str());
+    self.string(&player.name);
+    self.bool(player.is_bot);
+  }
+
+  // Where the event was in the file isn't part of it, the same match can be chunked differently
+  fn elimination(&mut self, elim: &Elimination) {
+    self.player(&elim.eliminated);
+    self.player(&elim.eliminator);
+    self.hasher.update([elim.gun_type]);
+    self.bool(elim.is_knocked);
+    self.u32(elim.timestamp);
+    self.option(elim.eliminated_position, |hasher, (x, y, z)| { hasher.f32(x); hasher.f32(y); hasher.f32(z); });
+    self.option(elim.eliminator_position, |hasher, (x, y, z)| { hasher.f32(x); hasher.f32(y); hasher.f32(z); });
+  }
+
+  fn match_stats(&mut self, stats: &MatchStats) {
+    self.f32(stats.accuracy);
+    for value in [
+      stats.assists, stats.eliminations, stats.weapon_damage, stats.other_damage, stats.revives, stats.damage_taken,
+      stats.damage_to_structures, stats.materials_gathered, stats.materials_used, stats.total_traveled
+    ] {
+      self.u32(value);
+    }
+  }
+
+  fn team_match_stats(&mut self, stats: &TeamMatchStats) {
+    self.u32(stats.placement);
+    self.u32(stats.total_players);
+  }
+}
+
+impl Replay {
+  // SHA-256 over the meta (without the name and timestamp), the header, the eliminations and the match and team
+  // stats. Two files of the same recording hash the same even if they were renamed or chunked differently
+  pub fn content_hash(&self) -> [u8; 32] {
+    let mut hasher = ContentHasher { hasher: Sha256::new() };
+
+    hasher.meta(&self.meta);
+    hasher.header(&self.header);
+    hasher.u32(self.eliminations.len() as u32);
+    for elim in self.eliminations.iter() {
+      hasher.elimination(elim);
+    }
+    hasher.option(self.match_stats.as_ref(), |hasher, stats| hasher.match_stats(stats));
+    hasher.option(self.team_match_stats.as_ref(), |hasher, stats| hasher.team_match_stats(stats));
+
+    return hasher.hasher.finalize().into();
+  }
+
+  pub fn content_hash_hex(&self) -> String {
+    return self.content_hash().iter().map(|byte| format!("{:02x}", byte)).collect();
+  }
+}
@@ -0,0 +1,17 @@
+#![allow(clippy::needless_return)]
+
+#[macro_use]
+mod logging;
+
+#[cfg(feature = "std")]
+pub mod batch;
+pub mod bit_reader;
+pub mod builder;
+pub mod compression;
+#[cfg(feature = "hash")]
+mod content_hash;
+pub mod parser;
+pub mod prelude;
+pub mod reader;
+pub mod version;
+pub mod weapons;
@@ -0,0 +1,21 @@
+// Logging through the log crate when the log feature is enabled. Without it the macros expand to a branch
+// that is never taken, so the arguments are still type checked but nothing is formatted or kept in the binary
+#[cfg(feature = "log")]
+macro_rules! log_debug {
+  ($($arg:tt)*) => { log::debug!($($arg)*) };
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! log_debug {
+  ($($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
+}
+
+#[cfg(feature = "log")]
+macro_rules! log_trace {
+  ($($arg:tt)*) => { log::trace!($($arg)*) };
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! log_trace {
+  ($($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
+}
@@ -1,27 +1,275 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::parser;
 use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+const USAGE: &str = "Usage: replay-reader <replay file> [--format text|csv|json] [-o <output file>]
+       replay-reader dump --events <output directory> <replay file>";
+
+enum OutputFormat {
+  Text,
+  Csv,
+  #[cfg(feature = "json")]
+  Json
+}
 
-mod parser;
-mod reader;
+struct Options {
+  file_path: String,
+  format: OutputFormat,
+  output_path: Option<String>
+}
 
-fn parse_replay_file(path: &str) {
-  let mut psr = parser::Parser::new(&path);
-  psr.parse();
+struct DumpOptions {
+  file_path: String,
+  events_dir: String
+}
 
-  for elim in psr.eliminations.iter() {
-    println!("[{}]: {} eliminated {}", elim.timestamp, elim.eliminator.id, elim.eliminated.id);
+enum Command {
+  Eliminations(Options),
+  Dump(DumpOptions)
+}
+
+fn parse_command(args: &[String]) -> Result<Command, String> {
+  if args.first().map(|arg| arg.as_str()) == Some("dump") {
+    return parse_dump_args(&args[1..]).map(Command::Dump);
   }
+
+  return parse_args(args).map(Command::Eliminations);
+}
+
+fn parse_dump_args(args: &[String]) -> Result<DumpOptions, String> {
+  let mut file_path = None;
+  let mut events_dir = None;
+
+  let mut args = args.iter();
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--events" => {
+        events_dir = match args.next() {
+          Some(value) => Some(value.clone()),
+          None => return Err(String::from("--events needs a directory"))
+        };
+      },
+      _ if file_path.is_none() => file_path = Some(arg.clone()),
+      _ => return Err(format!("Unexpected argument {}", arg))
+    }
+  }
+
+  return match (file_path, events_dir) {
+    (Some(file_path), Some(events_dir)) => Ok(DumpOptions { file_path, events_dir }),
+    (None, _) => Err(String::from("Please specify a replay file path")),
+    (_, None) => Err(String::from("Please specify what to dump, e.g. --events <output directory>"))
+  };
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+  let mut file_path = None;
+  let mut format = OutputFormat::Text;
+  let mut output_path = None;
+
+  let mut args = args.iter();
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--format" | "-f" => {
+        format = match args.next().map(|value| value.as_str()) {
+          Some("text") => OutputFormat::Text,
+          Some("csv") => OutputFormat::Csv,
+          #[cfg(feature = "json")]
+          Some("json") => OutputFormat::Json,
+          #[cfg(not(feature = "json"))]
+          Some("json") => return Err(String::from("JSON output needs the json feature (cargo build --features json)")),
+          Some(value) => return Err(format!("Unknown format {}", value)),
+          None => return Err(String::from("--format needs a value"))
+        };
+      },
+      "--output" | "-o" => {
+        output_path = match args.next() {
+          Some(value) => Some(value.clone()),
+          None => return Err(String::from("-o needs a file path"))
+        };
+      },
+      _ if file_path.is_none() => file_path = Some(arg.clone()),
+      _ => return Err(format!("Unexpected argument {}", arg))
+    }
+  }
+
+  return match file_path {
+    Some(file_path) => Ok(Options { file_path, format, output_path }),
+    None => Err(String::from("Please specify a replay file path"))
+  };
+}
+
+fn write_text(out: &mut dyn Write, psr: &parser::Parser) -> io::Result<()> {
+  for elim in psr {
+    writeln!(out, "[{}]: {} eliminated {}", elim.timestamp, elim.eliminator.id, elim.eliminated.id)?;
+  }
+
+  return Ok(());
+}
+
+// Quotes fields containing separators, quotes or line breaks, bot names can contain any of them
+fn csv_field(value: &str) -> String {
+  if value.contains([',', '"', '\n', '\r']) {
+    return format!("\"{}\"", value.replace('"', "\"\""));
+  }
+
+  return String::from(value);
+}
+
+fn write_csv(out: &mut dyn Write, psr: &parser::Parser) -> io::Result<()> {
+  writeln!(out, "timestamp,eliminator_id,eliminator_name,eliminated_id,eliminated_name,weapon,knocked")?;
+
+  for elim in psr {
+    writeln!(
+      out,
+      "{},{},{},{},{},{},{}",
+      elim.timestamp,
+      csv_field(elim.eliminator.id.as_str()),
+      csv_field(&elim.eliminator.name),
+      csv_field(elim.eliminated.id.as_str()),
+      csv_field(&elim.eliminated.name),
+      csv_field(&format!("{}", elim.death_cause)),
+      elim.is_knocked
+    )?;
+  }
+
+  return Ok(());
+}
+
+#[cfg(feature = "json")]
+fn write_json(out: &mut dyn Write, psr: &parser::Parser) -> io::Result<()> {
+  serde_json::to_writer_pretty(&mut *out, &psr.eliminations)?;
+  return writeln!(out);
+}
+
+// Goes to stderr so it doesn't end up in the csv or json output, and shows up even without eliminations
+fn summary_line(psr: &parser::Parser) -> String {
+  let mut summary = match &psr.meta {
+    Some(meta) => {
+      let length = meta.length().as_secs();
+      format!("{} ({:02}:{:02})", meta.name, length / 60, length % 60)
+    },
+    None => String::from("Replay")
+  };
+
+  summary.push_str(&format!(": {} eliminations", psr.eliminations.len()));
+
+  // Both are only written for the recording player once their match is over, spectated
+  // replays and replays of players leaving early don't have them
+  match &psr.team_match_stats {
+    Some(team_stats) => summary.push_str(&format!(", placed {} of {}", team_stats.placement, team_stats.total_players)),
+    None => summary.push_str(", no placement recorded")
+  }
+
+  if let Some(stats) = &psr.match_stats {
+    summary.push_str(&format!(", {} by the recording player", stats.eliminations));
+  }
+
+  return summary;
+}
+
+fn parse_replay_file(options: &Options) {
+  let mut psr = match parser::Parser::new(&options.file_path) {
+    Ok(psr) => psr,
+    Err(err) => {
+      eprintln!("Failed to open {}: {}", options.file_path, err);
+      return;
+    }
+  };
+
+  if let Err(err) = psr.parse() {
+    eprintln!("Failed to parse replay: {}", err);
+    return;
+  }
+
+  eprintln!("{}", summary_line(&psr));
+  if !psr.fully_consumed() {
+    eprintln!("Warning: {} bytes after offset {} were not parsed", psr.leftover_bytes(), psr.reader.offset);
+  }
+  let stats = psr.chunk_stats;
+  eprintln!("Chunks: {} header bytes, {} replay data bytes, {} checkpoint bytes, {} event bytes", stats.header_bytes, stats.data_bytes, stats.checkpoint_bytes, stats.event_bytes);
+
+  let mut out: Box<dyn Write> = match &options.output_path {
+    Some(output_path) => match File::create(output_path) {
+      Ok(file) => Box::new(BufWriter::new(file)),
+      Err(err) => {
+        eprintln!("Failed to create {}: {}", output_path, err);
+        return;
+      }
+    },
+    None => Box::new(io::stdout().lock())
+  };
+
+  let written = match options.format {
+    OutputFormat::Text => write_text(&mut out, &psr),
+    OutputFormat::Csv => write_csv(&mut out, &psr),
+    #[cfg(feature = "json")]
+    OutputFormat::Json => write_json(&mut out, &psr)
+  };
+
+  // A closed pipe (e.g. piping into head) isn't worth reporting
+  match written.and_then(|_| out.flush()) {
+    Err(err) if err.kind() != io::ErrorKind::BrokenPipe => eprintln!("Failed to write eliminations: {}", err),
+    _ => {}
+  }
+}
+
+// e.g. 0042_1234567_playerElim_versionedEvent.bin: the chunk index, the file offset of the chunk, then the
+// event group and metadata. Anything that isn't safe in a file name becomes an underscore
+fn dump_file_name(event: &parser::RawEvent) -> String {
+  let safe = |value: &str| -> String {
+    return value.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' }).collect();
+  };
+
+  return format!("{:04}_{}_{}_{}.bin", event.info.location.index, event.info.location.offset, safe(&event.info.group), safe(&event.info.metadata));
+}
+
+fn dump_events(options: &DumpOptions) {
+  let mut psr = match parser::Parser::builder().collect_raw_events(true).build_from_path(&options.file_path) {
+    Ok(psr) => psr,
+    Err(err) => {
+      eprintln!("Failed to open {}: {}", options.file_path, err);
+      return;
+    }
+  };
+
+  if let Err(err) = psr.parse() {
+    eprintln!("Failed to parse replay: {}", err);
+    return;
+  }
+
+  if let Err(err) = fs::create_dir_all(&options.events_dir) {
+    eprintln!("Failed to create {}: {}", options.events_dir, err);
+    return;
+  }
+
+  for event in psr.raw_events() {
+    let path = Path::new(&options.events_dir).join(dump_file_name(event));
+    if let Err(err) = fs::write(&path, &event.buffer) {
+      eprintln!("Failed to write {}: {}", path.display(), err);
+      return;
+    }
+  }
+
+  eprintln!("Wrote {} event payloads to {}", psr.raw_events().len(), options.events_dir);
 }
 
 fn main() {
-  let start_args: Vec<String> = env::args().collect();
-  let file_path = start_args.get(1);
-  let file_path = match file_path {
-    Some(data) => data,
-    None => {
-      eprintln!("Please specify a replay file path");
+  let start_args: Vec<String> = env::args().skip(1).collect();
+  let command = match parse_command(&start_args) {
+    Ok(command) => command,
+    Err(err) => {
+      eprintln!("{}", err);
+      eprintln!("{}", USAGE);
       return;
     }
   };
 
-  parse_replay_file(file_path);
+  match command {
+    Command::Eliminations(options) => parse_replay_file(&options),
+    Command::Dump(options) => dump_events(&options)
+  }
 }
@@ -1,36 +1,243 @@
-use crate::reader::Reader;
-use regex::Regex;
+#![allow(dead_code)]
 
+use crate::builder::ParserBuilder;
+use crate::compression::{DecompressError, Decompressor};
+use crate::reader::{DecryptError, ReadSeek, Reader, ReaderError};
+use crate::version::{
+  ELIMINATION_ID_OFFSET, ELIMINATION_ID_OFFSET_4_2, ELIMINATION_ID_OFFSET_BEFORE_4_2, ELIMINATION_TRANSFORMS_OFFSET,
+  ELIMINATION_TRANSFORM_ROTATION_SIZE, ELIMINATION_TRANSFORM_SCALE_SIZE,
+  ENGINE_NET_VERSION_PLAYER_STRUCT_CHANGE, FILE_VERSION_COMPRESSION, FILE_VERSION_CUSTOM_VERSIONS,
+  FILE_VERSION_ENCRYPTION, FILE_VERSION_RECORDED_TIMESTAMP, FILE_VERSION_STREAM_CHUNK_TIMES, LATEST_FILE_VERSION,
+  MAJOR_VERSION_ELIMINATION_OFFSET_CHANGE, MAJOR_VERSION_PLAYER_STRUCT_CHANGE, MAX_SUPPORTED_ENGINE_NETWORK_VERSION,
+  MINOR_VERSION_ELIMINATION_OFFSET_CHANGE, NETWORK_VERSION_HEADER_GUID
+};
+use crate::weapons::DeathCause;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const DOTNET_TICKS_AT_UNIX_EPOCH: u64 = 621355968000000000;
+const DOTNET_TICKS_PER_SECOND: u64 = 10000000;
+// 2017-01-01 and 2100-01-01 as Unix timestamps, see Meta::recorded_since_unix_epoch
+const EARLIEST_PLAUSIBLE_TIMESTAMP: u64 = 1483228800;
+const LATEST_PLAUSIBLE_TIMESTAMP: u64 = 4102444800;
+
+const RELEASE_BRANCH_PREFIX: &str = "++Fortnite+Release-";
+
+fn leading_digits(value: &str) -> &str {
+  let end = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+  return &value[..end];
+}
+
+// (major, minor) from a branch like ++Fortnite+Release-13.40, which can also be part of a longer branch name.
+// Playtest and other internal builds use different branch names, those are reported as (0, 0),
+// as are numbers that don't fit into a u32
+pub fn parse_branch_version(branch: &str) -> (u32, u32) {
+  for (index, _) in branch.match_indices(RELEASE_BRANCH_PREFIX) {
+    let version = &branch[index + RELEASE_BRANCH_PREFIX.len()..];
+    let major = leading_digits(version);
+
+    let minor_start = &version[major.len()..];
+    if major.is_empty() || !minor_start.starts_with('.') {
+      continue;
+    }
+
+    let minor = leading_digits(&minor_start[1..]);
+    return (major.parse().unwrap_or(0), minor.parse().unwrap_or(0));
+  }
+
+  return (0, 0);
+}
+
+pub const REPLAY_MAGIC: u32 = 0x1CA2E27F;
+pub const HEADER_MAGIC: u32 = 0x2CF5A13D;
+// The meta of a file version 0 replay with an empty name: magic, file version, length, network version,
+// changelist, name length and live flag
+pub const MIN_META_SIZE: usize = 28;
+
+#[derive(Debug)]
+pub enum ParseError {
+  Reader(ReaderError),
+  Decrypt(DecryptError),
+  Decompress(DecompressError),
+  Io(io::Error),
+  MissingMeta,
+  MissingHeader,
+  UnsupportedFileVersion(u32),
+  BadMagic { found: u32, expected: u32 },
+  TooSmall { len: usize, minimum: usize },
+  InvalidChunkSize { offset: usize, size: i32, buffer_len: usize }
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    return match self {
+      ParseError::Reader(err) => write!(f, "{}", err),
+      ParseError::Decrypt(err) => write!(f, "{}", err),
+      ParseError::Decompress(err) => write!(f, "{}", err),
+      ParseError::Io(err) => write!(f, "{}", err),
+      ParseError::MissingMeta => write!(f, "replay meta has not been parsed"),
+      ParseError::MissingHeader => write!(f, "header not found in replay chunks"),
+      ParseError::UnsupportedFileVersion(version) => write!(f, "file version {} is newer than the latest supported version {}", version, LATEST_FILE_VERSION),
+      ParseError::BadMagic { found, expected } => write!(f, "found magic {:#010x} instead of {:#010x}, this is not a replay file", found, expected),
+      ParseError::TooSmall { len, minimum } => write!(f, "file is only {} bytes long, a replay has at least {} bytes", len, minimum),
+      ParseError::InvalidChunkSize { offset, size, buffer_len } => write!(f, "chunk at offset {} has an invalid size of {} bytes (buffer is {} bytes long)", offset, size, buffer_len)
+    }
+  }
+}
+
+impl Error for ParseError {}
+
+impl From<ReaderError> for ParseError {
+  fn from(err: ReaderError) -> Self {
+    return ParseError::Reader(err);
+  }
+}
+
+impl From<DecryptError> for ParseError {
+  fn from(err: DecryptError) -> Self {
+    return ParseError::Decrypt(err);
+  }
+}
+
+impl From<io::Error> for ParseError {
+  fn from(err: io::Error) -> Self {
+    return ParseError::Io(err);
+  }
+}
+
+impl From<DecompressError> for ParseError {
+  fn from(err: DecompressError) -> Self {
+    return ParseError::Decompress(err);
+  }
+}
+
+// Returning true marks the event as handled and skips the built-in handling
+pub type EventHandler = Box<dyn FnMut(&EventInfo, &mut Reader) -> bool + Send>;
+
+// Decides from the event (or checkpoint) info whether its payload is read at all, returning false skips it
+// before it's decrypted. Checkpoints are passed with the group and metadata from the file, the group is
+// "checkpoint" for every checkpoint in 1.replay
+pub type EventFilter = Box<dyn Fn(&EventInfo) -> bool + Send>;
+
+// Called with the current offset and the total buffer length after each chunk
+pub type ProgressCallback = Box<dyn FnMut(usize, usize) + Send>;
+
+// Parsers don't share any state, so each one can be moved to its own thread
+const _: fn() = || {
+  fn assert_send<T: Send>() {}
+  assert_send::<Parser>();
+  assert_send::<Reader>();
+};
+
+// Entry point for untrusted input, every malformed replay ends up as an error instead of a panic
+pub fn parse_bytes_safe(data: &[u8]) -> Result<ReplaySummary, ParseError> {
+  return Parser::from_bytes(data.to_vec()).parse_to_summary();
+}
+
+// Not Clone, the reader can hold a whole replay or a stream and the handlers are closures.
+// The parsed fields are all Clone, clone those instead
 pub struct Parser {
   pub reader: Reader,
   pub meta: Option<Meta>,
   pub header: Option<Header>,
   pub match_stats: Option<MatchStats>,
   pub team_match_stats: Option<TeamMatchStats>,
+  pub eliminations: Vec<Elimination>,
+  pub players: HashMap<PlayerId, Player>,
+  // Bots have no id, so they are kept apart and keyed by name instead
+  pub bots: HashMap<String, Player>,
+  pub replay_data: Vec<ReplayData>,
+  pub checkpoints: Vec<Checkpoint>,
+  // Keys from PlayerStateEncryptionKey events, by event id since the payload doesn't name a player
+  pub player_state_keys: HashMap<String, [u8; 32]>,
+  pub raw_events: Vec<RawEvent>,
+  pub chunks: Vec<ChunkInfo>,
+  pub chunk_stats: ChunkStats,
+  pub decompressor: Option<Box<dyn Decompressor>>,
+  pub event_handlers: Vec<(String, EventHandler)>,
+  pub progress: Option<ProgressCallback>,
+  pub event_filter: Option<EventFilter>,
+  // Stores every event as a RawEvent without interpreting it, optionally without decrypting it first
+  pub collect_raw_events: bool,
+  pub decrypt_raw_events: bool,
+  // Makes parse() stop after the meta section
+  pub meta_only: bool,
+  // The chunk being parsed, still set to the failing chunk after parse() returns an error
+  pub current_chunk: ChunkLocation,
+  pub encryption_key_source: Option<KeySource>,
+  // Where the first chunk starts, set by parse_meta
+  pub chunks_offset: usize,
+  // Set when parse_chunks stopped at the incomplete last chunk of a live replay, or early in lenient mode
+  pub is_partial: bool,
+  pub parse_mode: ParseMode,
+  // Chunks a lenient parse skipped, with the error that made it skip them. Compressed replay data chunks are
+  // skipped without a decompressor in strict mode as well
+  pub skipped_chunks: Vec<SkippedChunk>
+}
+
+// Everything a successful parse produced, see Parser::finish
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Replay {
+  pub meta: Meta,
+  pub header: Header,
+  pub match_stats: Option<MatchStats>,
+  pub team_match_stats: Option<TeamMatchStats>,
+  pub eliminations: Vec<Elimination>,
+  pub players: Vec<Player>, // in roster() order
+  pub player_state_keys: HashMap<String, [u8; 32]>,
+  pub replay_data: Vec<ReplayData>,
+  pub checkpoints: Vec<Checkpoint>,
+  pub raw_events: Vec<RawEvent>,
+  pub chunks: Vec<ChunkInfo>
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct ReplaySummary {
+  pub meta: Meta,
+  pub header: Header,
+  pub match_stats: Option<MatchStats>,
+  pub team_match_stats: Option<TeamMatchStats>,
   pub eliminations: Vec<Elimination>
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
 pub struct Meta {
   pub magic: u32,
   pub file_version: u32,
+  pub custom_versions: Vec<(String, i32)>, // engine custom version guid and number
   pub length_in_ms: u32,
   pub network_version: u32,
+  // Written by the replay streamer when the recording started, to check replays against the client that plays
+  // them back. Use Replay::changelist, the header one is the build the match was played on
   pub changelist: u32,
   pub name: String,
   pub is_live: bool,
-  pub timestamp: Option<u32>,
+  pub timestamp: Option<u64>, // .NET ticks (100ns intervals since 0001-01-01)
   pub is_compressed: bool,
   pub is_encrypted: bool
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
 pub struct GameVersion {
   pub branch: String,
   pub patch: u16,
-  pub changelist: u32,
+  pub changelist: u32, // of the engine version the game was built with, what patch notes refer to
   pub major: u32,
   pub minor: u32
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
 pub struct Header {
   pub magic: u32,
   pub network_version: u32,
@@ -39,94 +246,995 @@ pub struct Header {
   pub game_network_protocol: u32,
   pub id: Option<String>,
   pub version: GameVersion,
-  pub level_names_and_times: Vec<(String, u32)>,
+  pub level_names_and_times: Vec<(String, u32)>, // level name, replay time in ms when the level was loaded
   pub flags: u32,
   pub game_specific_data: Vec<String>
 }
 
-pub struct Player {
-  pub id: String,
-  pub name: String,
-  pub is_bot: bool
-}
+// Header.game_specific_data split into key=value tokens. SubGame is the only key seen in replays so far,
+// every other token ends up in other (tokens without a value map to an empty string)
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct GameSpecificData {
+  pub sub_game: Option<String>,
+  pub other: HashMap<String, String>,
+  pub raw: Vec<String>
+}
+
+// Header.flags decoded, bit by bit as in the engine's EReplayHeaderFlags:
+// 0 client recorded, 1 has streaming fixes, 2 delta checkpoints, 3 game specific frame data,
+// 4 replay connection, 5 actor prioritization enabled, 6 net relevancy enabled, 7 async recorded.
+// Bits 8 to 31 aren't used by the engine yet and stay in raw. Compression and encryption aren't
+// header flags, they're in Meta
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HeaderFlags {
+  pub raw: u32,
+  pub client_recorded: bool,
+  pub has_streaming_fixes: bool,
+  pub delta_checkpoints: bool,
+  pub game_specific_frame_data: bool,
+  pub replay_connection: bool,
+  pub actor_prioritization_enabled: bool,
+  pub net_relevancy_enabled: bool,
+  pub async_recorded: bool
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct ReplayData {
+  pub start: u32,
+  pub end: u32,
+  pub buffer: Vec<u8>
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Checkpoint {
+  pub id: String,
+  pub group: String,
+  pub metadata: String,
+  pub start_time: u32,
+  pub end_time: u32,
+  pub buffer: Vec<u8>,
+  pub is_compressed: bool
+}
+
+// Every chunk seen while parsing, the size doesn't include the 8 bytes of type and size
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug)]
+pub struct ChunkInfo {
+  pub chunk_type: u32,
+  pub size: u32,
+  pub offset: usize
+}
+
+// The payload bytes per chunk type, without the 8 bytes of type and size. Chunks of unknown types aren't counted
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChunkStats {
+  pub header_bytes: u64,
+  pub data_bytes: u64,
+  pub checkpoint_bytes: u64,
+  pub event_bytes: u64
+}
+
+// What happens when a chunk can't be parsed. Strict returns the error, Lenient records it in skipped_chunks and
+// continues with the next chunk, keeping everything parsed so far. A chunk size that doesn't fit the file
+// stops a lenient parse as well, since the next chunk can't be found without it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParseMode {
+  #[default]
+  Strict,
+  Lenient
+}
+
+#[derive(Debug)]
+pub struct SkippedChunk {
+  pub location: ChunkLocation,
+  pub error: ParseError
+}
+
+// Replay::diff, every delta is the other replay's value minus this one's
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayDiff {
+  pub match_stats: Option<MatchStatsDiff>, // None unless both replays have match stats
+  pub placement: Option<i64>, // from the team match stats, None unless both have them
+  pub total_players: Option<i64>,
+  pub eliminations: i64, // elimination events of the whole match, including knocks
+  // Players (not bots) that show up in both replays, sorted by id
+  pub shared_players: Vec<PlayerId>
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchStatsDiff {
+  pub accuracy: f32,
+  pub assists: i64,
+  pub eliminations: i64,
+  pub weapon_damage: i64,
+  pub other_damage: i64,
+  pub revives: i64,
+  pub damage_taken: i64,
+  pub damage_to_structures: i64,
+  pub materials_gathered: i64,
+  pub materials_used: i64,
+  pub total_traveled: i64
+}
+
+impl MatchStats {
+  pub fn diff(&self, other: &MatchStats) -> MatchStatsDiff {
+    let delta = |value: u32, other_value: u32| other_value as i64 - value as i64;
+
+    return MatchStatsDiff {
+      accuracy: other.accuracy - self.accuracy,
+      assists: delta(self.assists, other.assists),
+      eliminations: delta(self.eliminations, other.eliminations),
+      weapon_damage: delta(self.weapon_damage, other.weapon_damage),
+      other_damage: delta(self.other_damage, other.other_damage),
+      revives: delta(self.revives, other.revives),
+      damage_taken: delta(self.damage_taken, other.damage_taken),
+      damage_to_structures: delta(self.damage_to_structures, other.damage_to_structures),
+      materials_gathered: delta(self.materials_gathered, other.materials_gathered),
+      materials_used: delta(self.materials_used, other.materials_used),
+      total_traveled: delta(self.total_traveled, other.total_traveled)
+    };
+  }
+}
+
+// Where the key used for decrypting came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeySource {
+  File,
+  Caller
+}
+
+// Index of the chunk in the replay and the file offset of its type field
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ChunkLocation {
+  pub index: usize,
+  pub offset: usize
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct EventInfo {
+  pub id: String,
+  pub group: String,
+  pub metadata: String,
+  pub start_time: u32,
+  pub end_time: u32,
+  pub location: ChunkLocation
+}
+
+// Events this parser has no dedicated handling for, with their decrypted payload
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct RawEvent {
+  pub info: EventInfo,
+  pub buffer: Vec<u8>,
+  pub is_encrypted: bool
+}
+
+// See Replay::timeline. Only the parsed events that carry a replay time, checkpoints and player state keys aren't
+// part of it. Eliminations are the only ones so far, other event kinds get a variant once they're parsed
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub enum TimelineEvent {
+  Elimination(Elimination)
+}
+
+impl TimelineEvent {
+  pub fn timestamp(&self) -> u32 {
+    return match self {
+      TimelineEvent::Elimination(elim) => elim.timestamp
+    };
+  }
+
+  pub fn location(&self) -> ChunkLocation {
+    return match self {
+      TimelineEvent::Elimination(elim) => elim.location
+    };
+  }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PlayerId(String);
+
+impl PlayerId {
+  pub fn new(id: &str) -> Self {
+    return Self(String::from(id));
+  }
+
+  pub fn as_str(&self) -> &str {
+    return &self.0;
+  }
+
+  pub fn canonical(&self) -> String {
+    return self.0.to_lowercase();
+  }
+
+  pub fn is_empty(&self) -> bool {
+    return self.0.is_empty();
+  }
+}
+
+impl fmt::Display for PlayerId {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    return write!(f, "{}", self.0);
+  }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Player {
+  pub id: PlayerId,
+  pub name: String,
+  pub is_bot: bool
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Elimination {
+  pub eliminated: Player,
+  pub eliminator: Player,
+  pub gun_type: u8,
+  pub death_cause: DeathCause,
+  pub is_knocked: bool,
+  pub timestamp: u32, // ms since the recording started, the same clock as Meta.length_in_ms and the chunk times
+  pub match_start: u32, // ms into the recording the match level was loaded at, usually 0
+  pub location: ChunkLocation,
+  // World positions (x, y, z), only recorded from engine network version 11 / season 9 on. The eliminator's is all
+  // zero when the recording client didn't know where they were (far away players, the storm, fall damage)
+  pub eliminated_position: Option<(f32, f32, f32)>,
+  pub eliminator_position: Option<(f32, f32, f32)>,
+  // Whatever follows the u32 knocked flag. None in 1.replay, where the flag ends
+  // exactly at the end of the payload, but a version that adds fields after it ends up here
+  #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+  pub trailing_bytes: Option<Vec<u8>>
+}
+
+// See Parser::player_summary
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlayerSummary {
+  pub kills: usize,
+  pub deaths: usize,
+  pub knocks: usize,
+  // Assists are only recorded in the match stats of the recording player, None for everyone else and
+  // when the recording player can't be told
+  pub assists: Option<u32>
+}
+
+impl PlayerSummary {
+  // (kills + assists) / deaths, with deaths counting as at least 1 so a player that never died has a finite one
+  pub fn kda(&self) -> f64 {
+    let assists = self.assists.unwrap_or(0) as f64;
+    return (self.kills as f64 + assists) / self.deaths.max(1) as f64;
+  }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct TeamMatchStats {
+  pub placement: u32,
+  pub total_players: u32
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct MatchStats {
+  pub accuracy: f32,
+  pub assists: u32,
+  pub eliminations: u32,
+  pub weapon_damage: u32,
+  pub other_damage: u32,
+  pub revives: u32,
+  pub damage_taken: u32,
+  pub damage_to_structures: u32,
+  pub materials_gathered: u32,
+  pub materials_used: u32,
+  pub total_traveled: u32
+}
+
+#[derive(Clone, Debug)]
+pub struct ReplayCompatibility {
+  pub valid_magic: bool,
+  pub valid_header_magic: bool,
+  pub supported_engine_network_version: bool,
+  pub warnings: Vec<String>
+}
+
+impl Header {
+  // Levels are listed in the order they were loaded and the match map is loaded first (at 0ms)
+  pub fn primary_level(&self) -> Option<&str> {
+    return self.level_names_and_times.iter().map(|(name, _)| name.as_str()).find(|name| !name.is_empty());
+  }
+
+  pub fn primary_level_time(&self) -> Option<u32> {
+    return self.level_names_and_times.iter().find(|(name, _)| !name.is_empty()).map(|(_, time)| *time);
+  }
+
+  pub fn parsed_game_specific_data(&self) -> GameSpecificData {
+    return GameSpecificData::parse(&self.game_specific_data);
+  }
+
+  pub fn header_flags(&self) -> HeaderFlags {
+    return HeaderFlags::from_bits(self.flags);
+  }
+}
+
+impl HeaderFlags {
+  const KNOWN_BITS: u32 = 0xFF;
+
+  pub fn from_bits(raw: u32) -> Self {
+    return Self {
+      raw,
+      client_recorded: raw & 1 != 0,
+      has_streaming_fixes: raw & 2 != 0,
+      delta_checkpoints: raw & 4 != 0,
+      game_specific_frame_data: raw & 8 != 0,
+      replay_connection: raw & 16 != 0,
+      actor_prioritization_enabled: raw & 32 != 0,
+      net_relevancy_enabled: raw & 64 != 0,
+      async_recorded: raw & 128 != 0
+    };
+  }
+
+  // Bits set by a newer engine version this crate doesn't know about
+  pub fn unknown_bits(&self) -> u32 {
+    return self.raw & !Self::KNOWN_BITS;
+  }
+}
+
+impl GameSpecificData {
+  pub fn parse(data: &[String]) -> Self {
+    let mut sub_game = None;
+    let mut other = HashMap::new();
+
+    for token in data {
+      let (key, value) = match token.split_once('=') {
+        Some((key, value)) => (key.trim(), value.trim()),
+        None => (token.trim(), "")
+      };
+
+      match key {
+        "SubGame" => sub_game = Some(String::from(value)),
+        _ => {
+          other.insert(String::from(key), String::from(value));
+        }
+      }
+    }
+
+    return Self {
+      sub_game,
+      other,
+      raw: data.to_vec()
+    };
+  }
+
+  pub fn get(&self, key: &str) -> Option<&str> {
+    if key == "SubGame" {
+      return self.sub_game.as_deref();
+    }
+
+    return self.other.get(key).map(|value| value.as_str());
+  }
+}
+
+impl EventInfo {
+  // Both times are milliseconds since the recording started
+  pub fn start(&self) -> Duration {
+    return Duration::from_millis(self.start_time as u64);
+  }
+
+  pub fn end(&self) -> Duration {
+    return Duration::from_millis(self.end_time as u64);
+  }
+}
+
+impl Elimination {
+  pub fn time(&self) -> Duration {
+    return Duration::from_millis(self.timestamp as u64);
+  }
+
+  // The time since the match level was loaded. The same as time() unless the recording started before that,
+  // e.g. when it was started in the lobby
+  pub fn time_into_match(&self) -> Duration {
+    return Duration::from_millis(self.timestamp.saturating_sub(self.match_start) as u64);
+  }
+
+  // In Unreal units (cm). None without positions and when the eliminator's position wasn't known
+  pub fn distance(&self) -> Option<f32> {
+    let (x1, y1, z1) = self.eliminated_position?;
+    let (x2, y2, z2) = self.eliminator_position?;
+    if (x2, y2, z2) == (0.0, 0.0, 0.0) {
+      return None;
+    }
+
+    return Some(((x2 - x1).powi(2) + (y2 - y1).powi(2) + (z2 - z1).powi(2)).sqrt());
+  }
+}
+
+// Players are the same player when their ids match, names can be missing in some events.
+// Bots have no id, so they are told apart by name like in Parser::bots, which makes all anonymous bots equal
+impl PartialEq for Player {
+  fn eq(&self, other: &Self) -> bool {
+    if self.is_bot != other.is_bot {
+      return false;
+    }
+
+    if self.is_bot {
+      return self.name == other.name;
+    }
+
+    return self.id == other.id;
+  }
+}
+
+impl Eq for Player {}
+
+impl Hash for Player {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.is_bot.hash(state);
+
+    if self.is_bot {
+      self.name.hash(state);
+    }
+    else {
+      self.id.hash(state);
+    }
+  }
+}
+
+// Player records only carry the name for bots, everyone else is shown by id
+impl fmt::Display for Player {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.name.is_empty() {
+      return write!(f, "{}", self.id);
+    }
+
+    return write!(f, "{}", self.name);
+  }
+}
+
+impl fmt::Display for Elimination {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let seconds = self.time().as_secs();
+    let action = if self.is_knocked { "knocked" } else { "eliminated" };
+
+    return write!(f, "{} {} {} with {} at {:02}:{:02}", self.eliminator, action, self.eliminated, self.death_cause, seconds / 60, seconds % 60);
+  }
+}
+
+impl ReplayCompatibility {
+  pub fn is_supported(&self) -> bool {
+    return self.valid_magic && self.valid_header_magic && self.supported_engine_network_version;
+  }
+}
+
+impl Meta {
+  pub fn length(&self) -> Duration {
+    return Duration::from_millis(self.length_in_ms as u64);
+  }
+
+  // The timestamp as time since the Unix epoch. None for timestamps before 2017 (replays didn't exist yet)
+  // or from 2100 on, which only come from corrupt files
+  pub fn recorded_since_unix_epoch(&self) -> Option<Duration> {
+    let ticks = self.timestamp?.checked_sub(DOTNET_TICKS_AT_UNIX_EPOCH)?;
+    let since_epoch = Duration::new(ticks / DOTNET_TICKS_PER_SECOND, ((ticks % DOTNET_TICKS_PER_SECOND) * 100) as u32);
+
+    if since_epoch.as_secs() < EARLIEST_PLAUSIBLE_TIMESTAMP || since_epoch.as_secs() >= LATEST_PLAUSIBLE_TIMESTAMP {
+      return None;
+    }
+
+    return Some(since_epoch);
+  }
+
+  pub fn recorded_at(&self) -> Option<SystemTime> {
+    return UNIX_EPOCH.checked_add(self.recorded_since_unix_epoch()?);
+  }
+
+  #[cfg(feature = "chrono")]
+  pub fn recorded_at_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+    let since_epoch = self.recorded_since_unix_epoch()?;
+
+    return chrono::DateTime::from_timestamp(since_epoch.as_secs() as i64, since_epoch.subsec_nanos());
+  }
+}
+
+// The recording player isn't named in the meta, the header or any event, only in the compressed replay data.
+// The match stats are the recording player's though, so the owner is the one player whose kill count (see
+// Parser::kill_count) is the eliminations of the match stats. None without match stats or eliminations,
+// or when more than one player has that many kills
+fn infer_owner_id(eliminations: &[Elimination], match_stats: Option<&MatchStats>) -> Option<PlayerId> {
+  let owner_kills = match_stats?.eliminations as usize;
+  if owner_kills == 0 {
+    return None;
+  }
+
+  let mut kill_counts: HashMap<&PlayerId, usize> = HashMap::new();
+  for elim in eliminations.iter().filter(|elim| !elim.is_knocked && !elim.eliminator.is_bot && elim.eliminated.id != elim.eliminator.id) {
+    if !elim.eliminator.id.is_empty() {
+      *kill_counts.entry(&elim.eliminator.id).or_insert(0) += 1;
+    }
+  }
+
+  let mut candidates = kill_counts.into_iter().filter(|(_, kills)| *kills == owner_kills);
+  return match (candidates.next(), candidates.next()) {
+    (Some((id, _)), None) => Some(id.clone()),
+    _ => None
+  };
+}
+
+impl Replay {
+  pub fn owner_id(&self) -> Option<PlayerId> {
+    return infer_owner_id(&self.eliminations, self.match_stats.as_ref());
+  }
+
+  // The header's changelist. The one in the meta usually is the same, but it comes from the replay streamer rather
+  // than the game build, so the header is what identifies the patch
+  pub fn changelist(&self) -> u32 {
+    return self.header.version.changelist;
+  }
+
+  // Every parsed event with a replay time (see TimelineEvent) sorted by timestamp, events with the same timestamp
+  // stay in the order they were recorded in
+  pub fn timeline(&self) -> Vec<TimelineEvent> {
+    let mut timeline: Vec<TimelineEvent> = self.eliminations.iter().cloned().map(TimelineEvent::Elimination).collect();
+    timeline.sort_by_key(|event| {
+      let location = event.location();
+      return (event.timestamp(), location.index, location.offset);
+    });
+
+    return timeline;
+  }
+
+  // e.g. an earlier match diffed with a later one gives what changed since then
+  pub fn diff(&self, other: &Replay) -> ReplayDiff {
+    let match_stats = match (&self.match_stats, &other.match_stats) {
+      (Some(stats), Some(other_stats)) => Some(stats.diff(other_stats)),
+      _ => None
+    };
+    let (placement, total_players) = match (&self.team_match_stats, &other.team_match_stats) {
+      (Some(team_stats), Some(other_team_stats)) => (
+        Some(other_team_stats.placement as i64 - team_stats.placement as i64),
+        Some(other_team_stats.total_players as i64 - team_stats.total_players as i64)
+      ),
+      _ => (None, None)
+    };
+
+    let mut shared_players: Vec<PlayerId> = self.players.iter()
+      .filter(|player| !player.is_bot && other.players.iter().any(|other_player| !other_player.is_bot && other_player.id == player.id))
+      .map(|player| player.id.clone())
+      .collect();
+    shared_players.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+    return ReplayDiff {
+      match_stats,
+      placement,
+      total_players,
+      eliminations: other.eliminations.len() as i64 - self.eliminations.len() as i64,
+      shared_players
+    };
+  }
+}
+
+impl Parser {
+  #[cfg(feature = "std")]
+  pub fn new(path: &str) -> io::Result<Self> {
+    let reader = Reader::new(path)?;
+
+    return Ok(Self::from_reader(reader));
+  }
+
+  pub fn from_bytes(buffer: Vec<u8>) -> Self {
+    let reader = Reader::from_bytes(buffer);
+
+    return Self::from_reader(reader);
+  }
+
+  #[cfg(feature = "mmap")]
+  pub fn from_mmap(path: &str) -> io::Result<Self> {
+    let reader = Reader::from_mmap(path)?;
+
+    return Ok(Self::from_reader(reader));
+  }
+
+  pub fn from_stream<R: ReadSeek + 'static>(source: R) -> io::Result<Self> {
+    let reader = Reader::from_stream(source)?;
+
+    return Ok(Self::from_reader(reader));
+  }
+
+  // Loads the replay without blocking the async runtime. Parsing itself is CPU bound and still blocking,
+  // run parse() in tokio::task::spawn_blocking for big replays
+  #[cfg(feature = "tokio")]
+  pub async fn from_async_read<R: tokio::io::AsyncRead + Unpin>(mut source: R) -> io::Result<Self> {
+    use tokio::io::AsyncReadExt;
+
+    let mut buffer = vec![];
+    source.read_to_end(&mut buffer).await?;
+
+    return Ok(Self::from_bytes(buffer));
+  }
+
+  #[cfg(feature = "tokio")]
+  pub async fn from_path_async(path: &str) -> io::Result<Self> {
+    let buffer = tokio::fs::read(path).await?;
+
+    return Ok(Self::from_bytes(buffer));
+  }
+
+  pub fn builder() -> ParserBuilder {
+    return ParserBuilder::new();
+  }
+
+  fn from_reader(reader: Reader) -> Self {
+    return Self {
+      reader,
+      meta: None,
+      header: None,
+      match_stats: None,
+      team_match_stats: None,
+      eliminations: vec![],
+      players: HashMap::new(),
+      bots: HashMap::new(),
+      replay_data: vec![],
+      checkpoints: vec![],
+      player_state_keys: HashMap::new(),
+      raw_events: vec![],
+      chunks: vec![],
+      chunk_stats: ChunkStats::default(),
+      decompressor: None,
+      event_handlers: vec![],
+      progress: None,
+      event_filter: None,
+      collect_raw_events: false,
+      decrypt_raw_events: true,
+      meta_only: false,
+      current_chunk: ChunkLocation::default(),
+      encryption_key_source: None,
+      chunks_offset: 0,
+      is_partial: false,
+      parse_mode: ParseMode::Strict,
+      skipped_chunks: vec![]
+    }
+  }
+
+  // e.g. with_event_filter(|info| info.group == "playerElim") to only decrypt eliminations
+  pub fn with_event_filter<F>(mut self, filter: F) -> Self where F: Fn(&EventInfo) -> bool + Send + 'static {
+    self.event_filter = Some(Box::new(filter));
+    return self;
+  }
+
+  fn is_filtered_out(&self, info: &EventInfo) -> bool {
+    return match &self.event_filter {
+      Some(filter) => !filter(info),
+      None => false
+    };
+  }
+
+  pub fn with_progress<F>(mut self, callback: F) -> Self where F: FnMut(usize, usize) + Send + 'static {
+    self.progress = Some(Box::new(callback));
+    return self;
+  }
+
+  // Used instead of the key stored in the replay, e.g. when that one has been stripped
+  pub fn with_encryption_key(mut self, key: Vec<u8>) -> Result<Self, DecryptError> {
+    self.reader.set_encryption_key(key)?;
+    self.encryption_key_source = Some(KeySource::Caller);
+    return Ok(self);
+  }
+
+  // The AES key the replay data is decrypted with, which is enough to decrypt the whole replay,
+  // so treat it like the replay itself when storing or logging it. None for unencrypted replays
+  pub fn encryption_key(&self) -> Option<&[u8]> {
+    if self.meta.as_ref().is_some_and(|meta| !meta.is_encrypted) {
+      return None;
+    }
+
+    return self.reader.encryption_key.as_deref();
+  }
+
+  pub fn with_raw_events(mut self, decrypt: bool) -> Self {
+    self.collect_raw_events = true;
+    self.decrypt_raw_events = decrypt;
+    return self;
+  }
+
+  pub fn raw_events(&self) -> &[RawEvent] {
+    return &self.raw_events;
+  }
+
+  // Invalid characters in strings become U+FFFD instead of failing the parse
+  pub fn with_lossy_strings(mut self, lossy: bool) -> Self {
+    self.reader.lossy_strings = lossy;
+    return self;
+  }
+
+  pub fn set_decompressor(&mut self, decompressor: impl Decompressor + 'static) {
+    self.decompressor = Some(Box::new(decompressor));
+  }
+
+  // `key` is matched against both the group and the metadata of each event
+  pub fn on_event<F>(&mut self, key: &str, handler: F) where F: FnMut(&EventInfo, &mut Reader) -> bool + Send + 'static {
+    self.event_handlers.push((String::from(key), Box::new(handler)));
+  }
+
+  pub fn eliminations_iter(&self) -> impl Iterator<Item = &Elimination> {
+    return self.eliminations.iter();
+  }
+
+  // Includes knocks, check is_knocked to only get finished eliminations
+  pub fn eliminations_by<'a>(&'a self, id: &'a PlayerId) -> impl Iterator<Item = &'a Elimination> {
+    return self.eliminations.iter().filter(move |elim| elim.eliminator.id == *id);
+  }
+
+  pub fn eliminations_of<'a>(&'a self, id: &'a PlayerId) -> impl Iterator<Item = &'a Elimination> {
+    return self.eliminations.iter().filter(move |elim| elim.eliminated.id == *id);
+  }
+
+  // Eliminations between two players, bots on either side are left out
+  pub fn player_eliminations(&self) -> impl Iterator<Item = &Elimination> {
+    return self.eliminations.iter().filter(|elim| !elim.eliminated.is_bot && !elim.eliminator.is_bot);
+  }
+
+  // Everything player_eliminations leaves out, i.e. a bot eliminated or was eliminated
+  pub fn bot_eliminations(&self) -> impl Iterator<Item = &Elimination> {
+    return self.eliminations.iter().filter(|elim| elim.eliminated.is_bot || elim.eliminator.is_bot);
+  }
+
+  // Finished eliminations of other players, knocks and self eliminations (e.g. fall damage) don't count
+  pub fn kill_count(&self, id: &PlayerId) -> usize {
+    return self.eliminations_by(id).filter(|elim| !elim.is_knocked && elim.eliminated.id != *id).count();
+  }
+
+  // Kills as in kill_count, deaths are finished eliminations of the player (including self eliminations), knocks
+  // are the knocks the player dealt. Assists come from the match stats if the player is the owner_id
+  pub fn player_summary(&self, id: &PlayerId) -> PlayerSummary {
+    let assists = match &self.match_stats {
+      Some(stats) if self.owner_id().as_ref() == Some(id) => Some(stats.assists),
+      _ => None
+    };
+
+    return PlayerSummary {
+      kills: self.kill_count(id),
+      deaths: self.eliminations_of(id).filter(|elim| !elim.is_knocked).count(),
+      knocks: self.eliminations_by(id).filter(|elim| elim.is_knocked).count(),
+      assists
+    };
+  }
+
+  // See infer_owner_id, the id is inferred from the match stats
+  pub fn owner_id(&self) -> Option<PlayerId> {
+    return infer_owner_id(&self.eliminations, self.match_stats.as_ref());
+  }
+
+  // Finished eliminations per death cause, knocks are left out so a knocked and then finished player counts once
+  pub fn weapon_breakdown(&self) -> HashMap<DeathCause, usize> {
+    let mut breakdown = HashMap::new();
+    for elim in self.eliminations.iter().filter(|elim| !elim.is_knocked) {
+      *breakdown.entry(elim.death_cause).or_insert(0) += 1;
+    }
+
+    return breakdown;
+  }
+
+  // Ties go to the death cause that was used first
+  pub fn most_common_weapon(&self) -> Option<DeathCause> {
+    let breakdown = self.weapon_breakdown();
+    let mut most_common: Option<(DeathCause, usize)> = None;
+
+    for elim in self.eliminations.iter().filter(|elim| !elim.is_knocked) {
+      let count = breakdown[&elim.death_cause];
+      if most_common.is_none_or(|(_, most_common_count)| count > most_common_count) {
+        most_common = Some((elim.death_cause, count));
+      }
+    }
+
+    return most_common.map(|(death_cause, _)| death_cause);
+  }
+
+  pub fn into_eliminations(self) -> Vec<Elimination> {
+    return self.eliminations;
+  }
 
-pub struct Elimination {
-  pub eliminated: Player,
-  pub eliminator: Player,
-  pub gun_type: String,
-  pub is_knocked: bool,
-  pub timestamp: u32
-}
+  pub fn parse(&mut self) -> Result<(), ParseError> {
+    self.parse_meta()?;
+    if self.meta_only {
+      return Ok(());
+    }
 
-pub struct TeamMatchStats {
-  pub placement: u32,
-  pub total_players: u32
-}
+    self.parse_chunks()?;
+    return Ok(());
+  }
 
-pub struct MatchStats {
-  pub accuracy: f32,
-  pub assists: u32,
-  pub eliminations: u32,
-  pub weapon_damage: u32,
-  pub other_damage: u32,
-  pub revives: u32,
-  pub damage_taken: u32,
-  pub damage_to_structures: u32,
-  pub materials_gathered: u32,
-  pub materials_used: u32,
-  pub total_traveled: u32
-}
+  // Only reads the meta section, pair it with from_stream to avoid loading the rest of the file
+  pub fn parse_meta_only(&mut self) -> Result<&Meta, ParseError> {
+    if self.meta.is_none() {
+      self.reader.goto(&0)?;
+      self.parse_meta()?;
+    }
 
-impl Parser {
-  pub fn new(path: &str) -> Self {
-    let reader = Reader::new(path);
+    return self.meta.as_ref().ok_or(ParseError::MissingMeta);
+  }
 
-    return Self {
-      reader: reader,
-      meta: None,
-      header: None,
-      match_stats: None,
-      team_match_stats: None,
-      eliminations: vec![]
+  pub fn parse_to_summary(mut self) -> Result<ReplaySummary, ParseError> {
+    self.parse()?;
+
+    return Ok(ReplaySummary {
+      meta: self.meta.ok_or(ParseError::MissingMeta)?,
+      header: self.header.ok_or(ParseError::MissingHeader)?,
+      match_stats: self.match_stats,
+      team_match_stats: self.team_match_stats,
+      eliminations: self.eliminations
+    });
+  }
+
+  // Everything parsed so far as one document, missing parts are null:
+  // {
+  //   "meta": Meta, "header": Header,
+  //   "match_stats": MatchStats, "team_match_stats": TeamMatchStats,
+  //   "players": [Player], (roster() order, bots last)
+  //   "eliminations": [Elimination]
+  // }
+  // Field names are the struct field names, the README has an example
+  #[cfg(feature = "json")]
+  pub fn to_json_value(&self) -> serde_json::Value {
+    return serde_json::json!({
+      "meta": self.meta,
+      "header": self.header,
+      "match_stats": self.match_stats,
+      "team_match_stats": self.team_match_stats,
+      "players": self.roster(),
+      "eliminations": self.eliminations
+    });
+  }
+
+  #[cfg(feature = "json")]
+  pub fn to_json_string_pretty(&self) -> Result<String, serde_json::Error> {
+    return serde_json::to_string_pretty(&self.to_json_value());
+  }
+
+  // Parses the replay unless that already happened (e.g. through parse_available) and hands over the results
+  pub fn finish(mut self) -> Result<Replay, ParseError> {
+    if self.meta.is_none() {
+      self.parse()?;
     }
+
+    let players = self.roster().into_iter().cloned().collect();
+
+    return Ok(Replay {
+      meta: self.meta.ok_or(ParseError::MissingMeta)?,
+      header: self.header.ok_or(ParseError::MissingHeader)?,
+      match_stats: self.match_stats,
+      team_match_stats: self.team_match_stats,
+      eliminations: self.eliminations,
+      players,
+      player_state_keys: self.player_state_keys,
+      replay_data: self.replay_data,
+      checkpoints: self.checkpoints,
+      raw_events: self.raw_events,
+      chunks: self.chunks
+    });
   }
 
-  pub fn parse(&mut self) {
-    self.parse_meta();
-    self.parse_chunks();
+  // Checks the parsed meta and header against what this parser understands.
+  // Only wrong magic numbers make parsing fail, callers decide whether to trust the output of an unsupported replay
+  pub fn compatibility(&self) -> ReplayCompatibility {
+    let mut warnings: Vec<String> = vec![];
+
+    let valid_magic = match &self.meta {
+      Some(meta) => meta.magic == REPLAY_MAGIC,
+      None => false
+    };
+    if !valid_magic {
+      warnings.push(String::from("replay magic doesn't match, this is probably not a replay file"));
+    }
+
+    let (valid_header_magic, supported_engine_network_version) = match &self.header {
+      Some(header) => (header.magic == HEADER_MAGIC, header.engine_network_version <= MAX_SUPPORTED_ENGINE_NETWORK_VERSION),
+      None => (false, false)
+    };
+    if !valid_header_magic {
+      warnings.push(String::from("header is missing or its magic doesn't match"));
+    }
+    else if !supported_engine_network_version {
+      let engine_network_version = self.header.as_ref().unwrap().engine_network_version;
+      warnings.push(format!("engine network version {} is newer than the latest supported version {}", engine_network_version, MAX_SUPPORTED_ENGINE_NETWORK_VERSION));
+    }
+
+    return ReplayCompatibility {
+      valid_magic,
+      valid_header_magic,
+      supported_engine_network_version,
+      warnings
+    }
   }
 
-  pub fn parse_meta(&mut self) {
-    let magic = self.reader.read_u32();
-    let file_version = self.reader.read_u32();
-    let length_in_ms = self.reader.read_u32();
-    let network_version = self.reader.read_u32();
-    let changelist = self.reader.read_u32();
-    let name = String::from(self.reader.read_string().trim_end());
-    let is_live = self.reader.read_bool();
+  // [magic: u32][file version: u32]
+  // 7+: [custom versions: u32 count, (guid: 16 bytes, version: i32) * count]
+  // [length in ms: u32][network version: u32][changelist: u32][friendly name: string][is live: u32]
+  // 3+: [timestamp: u64]
+  // 2+: [is compressed: u32]
+  // 6+: [is encrypted: u32][encryption key: u32 length, bytes]
+  // Versions 0 and 1 only differ in how the friendly name was padded, which trim_end takes care of.
+  // Version 4 added the times to data chunks and 5 only changed the name encoding, neither touches this part
+  pub fn parse_meta(&mut self) -> Result<(), ParseError> {
+    // Empty and cut off files get their own error, short files that aren't replays at all still get BadMagic
+    let remaining = self.reader.remaining();
+    if remaining < MIN_META_SIZE && (remaining < 4 || self.reader.peek_u32()? == REPLAY_MAGIC) {
+      return Err(ParseError::TooSmall { len: remaining, minimum: MIN_META_SIZE });
+    }
+
+    let magic = self.reader.read_u32()?;
+    if magic != REPLAY_MAGIC {
+      return Err(ParseError::BadMagic { found: magic, expected: REPLAY_MAGIC });
+    }
+
+    let file_version = self.reader.read_u32()?;
+    log_debug!("meta: magic {:#x}, file version {}", magic, file_version);
+    if file_version > LATEST_FILE_VERSION {
+      return Err(ParseError::UnsupportedFileVersion(file_version));
+    }
+
+    let mut custom_versions = vec![];
+    if file_version >= FILE_VERSION_CUSTOM_VERSIONS {
+      custom_versions = self.reader.read_array(|reader| Ok((reader.read_id()?, reader.read_i32()?)))?;
+      log_debug!("meta: {} custom versions, ending at offset {}", custom_versions.len(), self.reader.offset);
+      log_trace!("meta: custom versions {:?}", custom_versions);
+    }
+
+    let length_in_ms = self.reader.read_u32()?;
+    let network_version = self.reader.read_u32()?;
+    let changelist = self.reader.read_u32()?;
+    let name = String::from(self.reader.read_string()?.trim_end());
+    let is_live = self.reader.read_bool()?;
+    log_debug!("meta: length {}ms, network version {}, changelist {}, live {}", length_in_ms, network_version, changelist, is_live);
     
     let mut timestamp = None;
-    if file_version >= 3 {
-      timestamp = Some(((self.reader.read_u64() - 621355968000000000) / 100000) as u32);
+    if file_version >= FILE_VERSION_RECORDED_TIMESTAMP {
+      timestamp = Some(self.reader.read_u64()?);
+      log_trace!("meta: timestamp {:?}", timestamp);
     }
 
     let mut is_compressed = false;
-    if file_version >= 2 {
-      is_compressed = self.reader.read_bool();
+    if file_version >= FILE_VERSION_COMPRESSION {
+      is_compressed = self.reader.read_bool()?;
+      log_debug!("meta: compressed {}", is_compressed);
     }
 
     let mut is_encrypted = false;
-    if file_version >= 6 {
-      is_encrypted = self.reader.read_bool();
-      if is_encrypted {
-        let key_length = self.reader.read_u32();
-        self.reader.encryption_key = Some(self.reader.read_bytes(&(key_length as usize)).to_vec());
+    if file_version >= FILE_VERSION_ENCRYPTION {
+      is_encrypted = self.reader.read_bool()?;
+
+      // the key array is always present, it's just empty for unencrypted replays
+      let key_length = self.reader.read_u32()?;
+      let key = self.reader.read_bytes_owned(&(key_length as usize))?;
+      log_debug!("meta: encrypted {}, {} byte key, meta ends at offset {}", is_encrypted, key_length, self.reader.offset);
+      if is_encrypted && self.reader.encryption_key.is_none() {
+        self.reader.encryption_key = Some(key);
+        self.encryption_key_source = Some(KeySource::File);
+      }
+      else if is_encrypted {
+        self.encryption_key_source = Some(KeySource::Caller);
       }
     }
     
+    if !is_encrypted {
+      self.encryption_key_source = None;
+    }
+
+    self.chunks_offset = self.reader.offset;
+
     self.meta = Some(Meta {
       magic,
       file_version,
+      custom_versions,
       length_in_ms,
       network_version,
       changelist,
@@ -136,68 +1244,272 @@ impl Parser {
       is_compressed,
       is_encrypted
     });
+
+    return Ok(());
   }
 
-  pub fn parse_chunks(&mut self) {
-    while self.header.is_none() && self.reader.buffer.len() > self.reader.offset {
-      let chunk_type = self.reader.read_u32();
-      let chunk_size = self.reader.read_i32();
-      let start_offset = self.reader.offset;
+  pub fn parse_chunks(&mut self) -> Result<(), ParseError> {
+    let is_live = self.meta.as_ref().is_some_and(|meta| meta.is_live);
 
-      if chunk_type == 0 {
-        self.header = Some(self.parse_header());
-        self.reader.offset = start_offset + chunk_size as usize;
+    while self.reader.len() > self.reader.offset {
+      // Live replays are still being written, so the last chunk may not be complete yet
+      if is_live && !self.next_chunk_is_complete()? {
+        log_debug!("live replay: stopping at the incomplete chunk at offset {}", self.reader.offset);
+        self.is_partial = true;
+        break;
+      }
+
+      match self.parse_next_chunk() {
+        Err(err) if self.parse_mode == ParseMode::Lenient => {
+          log_debug!("chunk {}: stopping the lenient parse: {}", self.current_chunk.index, err);
+          self.skipped_chunks.push(SkippedChunk { location: self.current_chunk, error: err });
+          self.is_partial = true;
+          break;
+        },
+        result => result?
       }
     }
 
     if self.header.is_none() {
-      panic!("Header not found in replay chunks");
+      return Err(ParseError::MissingHeader);
     }
 
-    while self.reader.buffer.len() > self.reader.offset {
-      let chunk_type = self.reader.read_u32();
-      let chunk_size = self.reader.read_i32();
-      let start_offset = self.reader.offset;
+    return Ok(());
+  }
 
-      match chunk_type {
-        0 => { /* Header, parsed above */ },
-        1 => { /* Replay Data */ },
-        2 => { /* Checkpoint */ },
-        3 => {
-          self.parse_event();
-        }
-        _ => {}
+  // For replays that are still being written: parses the meta once it's complete and then every complete chunk,
+  // leaving the offset at the first chunk that hasn't fully arrived. Call it again after append() to continue,
+  // it returns the number of chunks parsed by this call
+  pub fn parse_available(&mut self) -> Result<usize, ParseError> {
+    if self.meta.is_none() {
+      self.reader.goto(&0)?;
+
+      match self.parse_meta() {
+        Ok(()) => {},
+        Err(ParseError::Reader(ReaderError::OutOfBounds { .. })) | Err(ParseError::TooSmall { .. }) => {
+          self.reader.goto(&0)?;
+          return Ok(0);
+        },
+        Err(err) => return Err(err)
       }
+    }
+
+    let mut parsed_chunks = 0;
+    while self.next_chunk_is_complete()? {
+      self.parse_next_chunk()?;
+      parsed_chunks += 1;
+    }
 
-      self.reader.offset = start_offset + chunk_size as usize;
+    return Ok(parsed_chunks);
+  }
+
+  pub fn append(&mut self, bytes: &[u8]) -> Result<(), ReaderError> {
+    return self.reader.append(bytes);
+  }
+
+  // Negative sizes count as complete so parse_next_chunk reports them
+  fn next_chunk_is_complete(&mut self) -> Result<bool, ParseError> {
+    if self.reader.remaining() < 8 {
+      return Ok(false);
     }
+
+    let chunk_header = self.reader.peek_bytes(&8)?;
+    let chunk_size = i32::from_le_bytes([chunk_header[4], chunk_header[5], chunk_header[6], chunk_header[7]]);
+    if chunk_size < 0 {
+      return Ok(true);
+    }
+
+    return Ok(chunk_size as usize <= self.reader.remaining() - 8);
   }
 
-  pub fn parse_header(&mut self) -> Header {
-    let magic = self.reader.read_u32();
-    let network_version = self.reader.read_u32();
-    let network_checksum = self.reader.read_u32();
-    let engine_network_version = self.reader.read_u32();
-    let game_network_protocol = self.reader.read_u32();
+  // Chunks before the header are skipped, like the engine does
+  fn parse_next_chunk(&mut self) -> Result<(), ParseError> {
+    self.current_chunk = ChunkLocation { index: self.chunks.len(), offset: self.reader.offset };
 
-    let mut id: Option<String> = None;
-    if network_version > 12 {
-      id = Some(self.reader.read_id());
+    let chunk_type = self.reader.read_u32()?;
+    let chunk_size = self.reader.read_i32()?;
+    let start_offset = self.reader.offset;
+    let end_offset = self.chunk_end(start_offset, chunk_size)?;
+    self.record_chunk(chunk_type, chunk_size);
+    log_debug!("chunk {}: type {} at offset {}, {} bytes", self.current_chunk.index, chunk_type, self.current_chunk.offset, chunk_size);
+
+    if let Err(err) = self.parse_chunk_payload(chunk_type, chunk_size) {
+      if self.parse_mode == ParseMode::Strict {
+        return Err(err);
+      }
+
+      log_debug!("chunk {}: skipped: {}", self.current_chunk.index, err);
+      self.skipped_chunks.push(SkippedChunk { location: self.current_chunk, error: err });
+    }
+
+    self.reader.offset = end_offset;
+    self.report_progress();
+
+    return Ok(());
+  }
+
+  fn parse_chunk_payload(&mut self, chunk_type: u32, chunk_size: i32) -> Result<(), ParseError> {
+    if self.header.is_none() {
+      if chunk_type == 0 {
+        self.header = Some(self.parse_header()?);
+      }
+
+      return Ok(());
+    }
+
+    match chunk_type {
+      0 => { /* Only the first header is used */ },
+      1 => {
+        self.parse_replay_data(chunk_size as usize)?;
+      },
+      2 => {
+        self.parse_checkpoint()?;
+      },
+      3 => {
+        self.parse_event()?;
+      }
+      _ => {
+        log_debug!("chunk {}: skipping unknown chunk type {}", self.current_chunk.index, chunk_type);
+      }
+    }
+
+    return Ok(());
+  }
+
+  // Called once the size has been validated by chunk_end
+  fn record_chunk(&mut self, chunk_type: u32, chunk_size: i32) {
+    let size = chunk_size as u64;
+    match chunk_type {
+      0 => self.chunk_stats.header_bytes += size,
+      1 => self.chunk_stats.data_bytes += size,
+      2 => self.chunk_stats.checkpoint_bytes += size,
+      3 => self.chunk_stats.event_bytes += size,
+      _ => {}
+    }
+
+    self.chunks.push(ChunkInfo {
+      chunk_type,
+      size: chunk_size as u32,
+      offset: self.current_chunk.offset
+    });
+  }
+
+  pub fn chunks(&self) -> &[ChunkInfo] {
+    return &self.chunks;
+  }
+
+  // Bytes after the offset the parse stopped at. A complete parse always ends at the end of the last chunk,
+  // bytes left over after it mean the chunk loop stopped early (meta_only, a partial live replay) or a misparse
+  pub fn leftover_bytes(&self) -> usize {
+    return self.reader.remaining();
+  }
+
+  pub fn fully_consumed(&self) -> bool {
+    return self.leftover_bytes() == 0;
+  }
+
+  // Walks the chunk headers from the start without parsing any chunk and returns the first chunk of the type.
+  // Only the meta is parsed (if it hasn't been yet), the reader offset is restored afterwards
+  pub fn find_chunk(&mut self, chunk_type: u32) -> Result<Option<ChunkInfo>, ParseError> {
+    let offset = self.reader.offset;
+    let found = self.scan_chunks(chunk_type);
+    self.reader.offset = offset;
+
+    return found;
+  }
+
+  fn scan_chunks(&mut self, chunk_type: u32) -> Result<Option<ChunkInfo>, ParseError> {
+    if self.meta.is_none() {
+      self.reader.goto(&0)?;
+      self.parse_meta()?;
+    }
+
+    self.reader.goto(&self.chunks_offset)?;
+
+    while self.reader.remaining() >= 8 {
+      let offset = self.reader.offset;
+      let current_type = self.reader.read_u32()?;
+      let chunk_size = self.reader.read_i32()?;
+      let end_offset = self.chunk_end(self.reader.offset, chunk_size)?;
+
+      if current_type == chunk_type {
+        return Ok(Some(ChunkInfo { chunk_type, size: chunk_size as u32, offset }));
+      }
+
+      self.reader.offset = end_offset;
+    }
+
+    return Ok(None);
+  }
+
+  // A reader over the payload of a chunk from find_chunk or chunks(), without the type and size.
+  // It gets the encryption key so event payloads can be decrypted with decrypt_buffer
+  pub fn read_chunk(&mut self, info: &ChunkInfo) -> Result<Reader, ParseError> {
+    let offset = self.reader.offset;
+    let start_offset = info.offset + 8;
+    self.chunk_end(start_offset, info.size as i32)?;
+
+    self.reader.goto(&start_offset)?;
+    let payload = self.reader.read_bytes_owned(&(info.size as usize));
+    self.reader.offset = offset;
+
+    let mut chunk_reader = Reader::from_bytes(payload?);
+    chunk_reader.encryption_key = self.reader.encryption_key.clone();
+    chunk_reader.lossy_strings = self.reader.lossy_strings;
+    chunk_reader.cipher_mode = self.reader.cipher_mode;
+    chunk_reader.big_endian = self.reader.big_endian;
+
+    return Ok(chunk_reader);
+  }
+
+  fn report_progress(&mut self) {
+    if let Some(progress) = &mut self.progress {
+      progress(self.reader.offset, self.reader.len());
+    }
+  }
+
+  fn chunk_end(&self, start_offset: usize, chunk_size: i32) -> Result<usize, ParseError> {
+    let buffer_len = self.reader.len();
+    let end_offset = start_offset.saturating_add(chunk_size.max(0) as usize);
+
+    if chunk_size < 0 || end_offset > buffer_len {
+      return Err(ParseError::InvalidChunkSize {
+        offset: start_offset,
+        size: chunk_size,
+        buffer_len
+      });
+    }
+
+    return Ok(end_offset);
+  }
+
+  pub fn parse_header(&mut self) -> Result<Header, ParseError> {
+    let magic = self.reader.read_u32()?;
+    if magic != HEADER_MAGIC {
+      return Err(ParseError::BadMagic { found: magic, expected: HEADER_MAGIC });
     }
 
-    self.reader.skip(&4);
-    let patch = self.reader.read_u16();
-    let changelist = self.reader.read_u32();
-    let branch = self.reader.read_string();
-    let level_names_and_times = self.reader.read_string_u32_tuple_vec();
-    let flags = self.reader.read_u32();
-    let game_specific_data = self.reader.read_string_vec();
+    let network_version = self.reader.read_u32()?;
+    let network_checksum = self.reader.read_u32()?;
+    let engine_network_version = self.reader.read_u32()?;
+    let game_network_protocol = self.reader.read_u32()?;
+
+    let mut id: Option<String> = None;
+    if network_version >= NETWORK_VERSION_HEADER_GUID {
+      id = Some(self.reader.read_id()?);
+    }
 
-    let re = Regex::new(r"\+\+Fortnite\+Release\-(?P<major>\d+)\.(?P<minor>\d*)").unwrap();
+    self.reader.skip(&4)?; // engine major and minor version (u16 each), the game version is in the branch
+    let patch = self.reader.read_u16()?;
+    let changelist = self.reader.read_u32()?;
+    let branch = self.reader.read_string()?;
+    let level_names_and_times = self.reader.read_string_u32_tuple_vec()?;
+    let flags = self.reader.read_u32()?;
+    let game_specific_data = self.reader.read_string_vec()?;
 
-    let version_data = re.captures(&branch).unwrap();
+    let (major, minor) = parse_branch_version(&branch);
 
-    return Header {
+    return Ok(Header {
       magic,
       network_version,
       network_checksum,
@@ -208,139 +1520,417 @@ impl Parser {
         branch: (*branch).to_string(),
         patch,
         changelist,
-        major: version_data["major"].parse().unwrap(),
-        minor: version_data["minor"].parse().unwrap()
+        major,
+        minor
       },
       level_names_and_times,
       flags,
       game_specific_data,
+    });
+  }
+
+  pub fn parse_replay_data(&mut self, chunk_size: usize) -> Result<(), ParseError> {
+    let meta = self.meta.as_ref().ok_or(ParseError::MissingMeta)?;
+    let file_version = meta.file_version;
+    // Replay data isn't needed for anything else, so this doesn't fail the parse like decompress_buffer would,
+    // but the chunk is recorded in skipped_chunks in either mode
+    if meta.is_compressed && self.decompressor.is_none() {
+      log_debug!("replay data: skipping compressed chunk, no decompressor was set");
+      let error = ParseError::Decompress(DecompressError::new("replay is compressed but no decompressor was set"));
+      self.skipped_chunks.push(SkippedChunk { location: self.current_chunk, error });
+      return Ok(());
+    }
+
+    let mut start = 0;
+    let mut end = 0;
+    let mut length = chunk_size as u32;
+    if file_version >= FILE_VERSION_STREAM_CHUNK_TIMES {
+      start = self.reader.read_u32()?;
+      end = self.reader.read_u32()?;
+      length = self.reader.read_u32()?;
+    }
+
+    if file_version >= FILE_VERSION_ENCRYPTION {
+      self.reader.skip(&4)?; // decompressed size in memory
+    }
+    log_debug!("replay data: {}ms to {}ms, {} bytes at offset {}", start, end, length, self.reader.offset);
+
+    let encrypted_buffer = self.reader.read_bytes_owned(&(length as usize))?;
+    let buffer_reader = self.decrypt_buffer(encrypted_buffer)?;
+    let data = self.decompress_buffer(buffer_reader)?;
+
+    self.replay_data.push(ReplayData {
+      start,
+      end,
+      buffer: data.into_buffer()
+    });
+
+    return Ok(());
+  }
+
+  pub fn parse_checkpoint(&mut self) -> Result<(), ParseError> {
+    let info = EventInfo {
+      id: self.reader.read_string()?,
+      group: self.reader.read_string()?,
+      metadata: self.reader.read_string()?,
+      start_time: self.reader.read_u32()?,
+      end_time: self.reader.read_u32()?,
+      location: self.current_chunk
+    };
+    let length = self.reader.read_u32()?;
+    log_debug!("checkpoint {} (group {}, metadata {}): {} bytes at offset {}", info.id, info.group, info.metadata, length, self.reader.offset);
+
+    // The rest of the chunk is skipped by parse_chunks
+    if self.is_filtered_out(&info) {
+      return Ok(());
+    }
+
+    let encrypted_buffer = self.reader.read_bytes_owned(&(length as usize))?;
+    let mut buffer_reader = self.decrypt_buffer(encrypted_buffer)?;
+
+    let mut is_compressed = self.meta.as_ref().ok_or(ParseError::MissingMeta)?.is_compressed;
+    if is_compressed && self.decompressor.is_some() {
+      buffer_reader = self.decompress_buffer(buffer_reader)?;
+      is_compressed = false;
+    }
+
+    self.checkpoints.push(Checkpoint {
+      id: info.id,
+      group: info.group,
+      metadata: info.metadata,
+      start_time: info.start_time,
+      end_time: info.end_time,
+      buffer: buffer_reader.into_buffer(),
+      is_compressed
+    });
+
+    return Ok(());
+  }
+
+  pub fn decrypt_buffer(&self, data: Vec<u8>) -> Result<Reader, ParseError> {
+    if !self.meta.as_ref().ok_or(ParseError::MissingMeta)?.is_encrypted {
+      log_trace!("decrypt: replay isn't encrypted, using the {} bytes as they are", data.len());
+      let mut reader = Reader::from_bytes(data);
+      reader.lossy_strings = self.reader.lossy_strings;
+      reader.big_endian = self.reader.big_endian;
+
+      return Ok(reader);
+    }
+
+    log_debug!("decrypt: {} bytes", data.len());
+    return Ok(self.reader.decrypt_buffer(data)?);
+  }
+
+  pub fn decompress_buffer(&self, mut data: Reader) -> Result<Reader, ParseError> {
+    if !self.meta.as_ref().ok_or(ParseError::MissingMeta)?.is_compressed {
+      return Ok(data);
+    }
+
+    let decompressor = match &self.decompressor {
+      Some(decompressor) => decompressor,
+      None => return Err(ParseError::Decompress(DecompressError::new("replay is compressed but no decompressor was set")))
+    };
+
+    let decompressed_size = data.read_i32()?;
+    let compressed_size = data.read_i32()?;
+    if decompressed_size < 0 || compressed_size < 0 {
+      return Err(ParseError::Decompress(DecompressError::new("compressed buffer has a negative size")));
     }
+
+    let compressed_buffer = data.read_bytes(&(compressed_size as usize))?;
+    let decompressed_buffer = decompressor.decompress(compressed_buffer, decompressed_size as usize)?;
+
+    let mut reader = Reader::from_bytes(decompressed_buffer);
+    reader.lossy_strings = data.lossy_strings;
+    reader.big_endian = data.big_endian;
+
+    return Ok(reader);
   }
 
-  pub fn parse_event(&mut self) {
-    self.reader.read_string();
-    let group = self.reader.read_string();
-    let metadata = self.reader.read_string();
-    let start_time = self.reader.read_u32();
-    self.reader.skip(&4);
-    let length = self.reader.read_u32();
+  pub fn parse_event(&mut self) -> Result<(), ParseError> {
+    let info = EventInfo {
+      id: self.reader.read_string()?,
+      group: self.reader.read_string()?,
+      metadata: self.reader.read_string()?,
+      start_time: self.reader.read_u32()?,
+      end_time: self.reader.read_u32()?,
+      location: self.current_chunk
+    };
+    let length = self.reader.read_u32()?;
+    log_debug!("event {} (group {}, metadata {}): {} bytes at offset {}", info.id, info.group, info.metadata, length, self.reader.offset);
+
+    // The rest of the chunk is skipped by parse_chunks
+    if self.is_filtered_out(&info) {
+      return Ok(());
+    }
+
+    let encrypted_buffer = self.reader.read_bytes_owned(&(length as usize))?;
+
+    if self.collect_raw_events && !self.decrypt_raw_events {
+      self.raw_events.push(RawEvent {
+        info,
+        buffer: encrypted_buffer,
+        is_encrypted: self.meta.as_ref().ok_or(ParseError::MissingMeta)?.is_encrypted
+      });
+
+      return Ok(());
+    }
+
+    let mut buffer_reader = self.decrypt_buffer(encrypted_buffer)?;
+
+    if self.collect_raw_events {
+      self.raw_events.push(RawEvent {
+        info,
+        buffer: buffer_reader.into_buffer(),
+        is_encrypted: false
+      });
+
+      return Ok(());
+    }
+
+    if buffer_reader.len() <= 64 {
+      log_trace!("event {}: {:02x?}", info.id, buffer_reader.buffer());
+    }
+
+    let mut handled = false;
+    for (key, handler) in self.event_handlers.iter_mut() {
+      if *key == info.group || *key == info.metadata {
+        handled |= handler(&info, &mut buffer_reader);
+        buffer_reader.goto(&0)?;
+      }
+    }
 
-    let encrypted_buffer = self.reader.read_bytes(&(length as usize)).to_vec();
-    let mut buffer_reader = self.reader.decrypt_buffer(encrypted_buffer);
+    if handled {
+      return Ok(());
+    }
 
-    if group == "playerElim" {
-      self.parse_elimination(&mut buffer_reader, start_time);
+    // Per hit, damage and storm events aren't parsed yet: none were seen in 1.replay, so their groups and layouts
+    // are unknown. If a replay has them they end up in raw_events and can be handled with on_event
+    if info.group == "playerElim" {
+      self.parse_elimination(&mut buffer_reader, info.start_time)?;
+    }
+    else if info.metadata == "AthenaMatchStats" {
+      self.match_stats = Some(self.parse_match_stats(&mut buffer_reader)?);
     }
-    else if metadata == "AthenaMatchStats" {
-      self.match_stats = Some(self.parse_match_stats(&mut buffer_reader));
+    else if info.metadata == "AthenaMatchTeamStats" {
+      self.team_match_stats = Some(self.parse_team_match_stats(&mut buffer_reader)?);
     }
-    else if metadata == "AthenaMatchTeamStats" {
-      self.team_match_stats = Some(self.parse_team_match_stats(&mut buffer_reader));
+    else if info.metadata == "PlayerStateEncryptionKey" {
+      let key = self.parse_player_state_key(&mut buffer_reader)?;
+      self.player_state_keys.insert(info.id, key);
     }
-    else if metadata == "PlayerStateEncryptionKey" {
-      // ignore
+    else {
+      self.raw_events.push(RawEvent {
+        info,
+        buffer: buffer_reader.into_buffer(),
+        is_encrypted: false
+      });
     }
+
+    return Ok(());
+  }
+
+  // [key: 32 bytes], the AES key for the replicated player state data.
+  // Recorded replays hold one of these for the recording player, with the id "<replay name>_ReplayKey"
+  pub fn parse_player_state_key(&mut self, data: &mut Reader) -> Result<[u8; 32], ReaderError> {
+    let mut key = [0u8; 32];
+    key.copy_from_slice(data.read_bytes(&32)?);
+    return Ok(key);
   }
 
-  pub fn parse_elimination(&mut self, data: &mut Reader, timestamp: u32) {
-    let header = &self.header.as_ref().unwrap();
+  pub fn parse_elimination(&mut self, data: &mut Reader, timestamp: u32) -> Result<(), ParseError> {
+    let header = self.header.as_ref().ok_or(ParseError::MissingHeader)?;
+    let match_start = header.primary_level_time().unwrap_or(0);
 
     #[allow(unused_assignments)]
     let mut eliminated = None;
     #[allow(unused_assignments)]
     let mut eliminator = None;
+    let mut eliminated_position = None;
+    let mut eliminator_position = None;
+
+    if header.engine_network_version >= ENGINE_NET_VERSION_PLAYER_STRUCT_CHANGE && header.version.major >= MAJOR_VERSION_PLAYER_STRUCT_CHANGE {
+      // The 85 byte block in front of the players, see its layout in the version module
+      data.skip(&ELIMINATION_TRANSFORMS_OFFSET)?;
+      eliminated_position = Some(self.parse_transform_position(data)?);
+      eliminator_position = Some(self.parse_transform_position(data)?);
 
-    if header.engine_network_version >= 11 && header.version.major >= 9 {
-      data.skip(&85);
-      eliminated = Some(self.parse_player(data));
-      eliminator = Some(self.parse_player(data));
+      eliminated = Some(self.parse_player(data)?);
+      eliminator = Some(self.parse_player(data)?);
     }
     else {
-      if header.version.major <= 4 && header.version.minor < 2 {
-        data.skip(&12);
+      if header.version.major <= MAJOR_VERSION_ELIMINATION_OFFSET_CHANGE && header.version.minor < MINOR_VERSION_ELIMINATION_OFFSET_CHANGE {
+        data.skip(&ELIMINATION_ID_OFFSET_BEFORE_4_2)?;
       }
-      else if header.version.major == 4 && header.version.minor <= 2 {
-        data.skip(&40);
+      else if header.version.major == MAJOR_VERSION_ELIMINATION_OFFSET_CHANGE && header.version.minor <= MINOR_VERSION_ELIMINATION_OFFSET_CHANGE {
+        data.skip(&ELIMINATION_ID_OFFSET_4_2)?;
       }
       else {
-        data.skip(&45);
+        data.skip(&ELIMINATION_ID_OFFSET)?;
       }
 
       eliminated = Some(Player {
         name: String::from(""),
-        id: data.read_string(),
+        id: PlayerId(data.read_string()?),
         is_bot: false
       });
       eliminator = Some(Player {
         name: String::from(""),
-        id: data.read_string(),
+        id: PlayerId(data.read_string()?),
         is_bot: false
       });
     }
 
-    let gun_type = data.read_byte();
-    let knocked = data.read_bool();
+    let gun_type = data.read_byte()?;
+    // A u32 in every replay we have (3.x to 13.40), like the other readers read it. Only a replay of a version
+    // that writes something else would be a reason to read it differently
+    let knocked = data.read_bool()?;
+
+    let mut trailing_bytes = None;
+    if !data.is_eof() {
+      log_debug!("elimination: {} unknown bytes after the knocked flag at offset {}", data.remaining(), self.current_chunk.offset);
+      trailing_bytes = Some(data.read_bytes_owned(&data.remaining())?);
+    }
+
+    let eliminated = eliminated.unwrap();
+    let eliminator = eliminator.unwrap();
+    self.record_player(&eliminated);
+    self.record_player(&eliminator);
 
     self.eliminations.push(Elimination {
-      eliminated: eliminated.unwrap(),
-      eliminator: eliminator.unwrap(),
-      gun_type: format!("{:02X?}", gun_type),
+      eliminated,
+      eliminator,
+      gun_type,
+      death_cause: DeathCause::from_byte(gun_type),
       is_knocked: knocked,
-      timestamp: timestamp
+      timestamp,
+      match_start,
+      location: self.current_chunk,
+      eliminated_position,
+      eliminator_position,
+      trailing_bytes
     });
+
+    return Ok(());
   }
 
-  pub fn parse_player(&mut self, data: &mut Reader) -> Player {
-    let player_type = data.read_byte();
-    
-    return match player_type {
+  // [rotation: 4 x f32][location: 3 x f32][scale: 3 x f32], only the location is kept
+  pub fn parse_transform_position(&mut self, data: &mut Reader) -> Result<(f32, f32, f32), ReaderError> {
+    data.skip(&ELIMINATION_TRANSFORM_ROTATION_SIZE)?;
+    let position = (data.read_f32()?, data.read_f32()?, data.read_f32()?);
+    data.skip(&ELIMINATION_TRANSFORM_SCALE_SIZE)?;
+
+    return Ok(position);
+  }
+
+  // Keeps the first record of every player, but fills in the name once a record has one
+  fn record_player(&mut self, player: &Player) {
+    let known = if player.is_bot {
+      self.bots.entry(player.name.clone()).or_insert_with(|| player.clone())
+    }
+    else {
+      if player.id.is_empty() {
+        return;
+      }
+
+      self.players.entry(player.id.clone()).or_insert_with(|| player.clone())
+    };
+
+    if known.name.is_empty() && !player.name.is_empty() {
+      known.name = player.name.clone();
+    }
+  }
+
+  // Players sorted by id followed by bots sorted by name
+  pub fn roster(&self) -> Vec<&Player> {
+    let mut players: Vec<&Player> = self.players.values().collect();
+    players.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
+
+    let mut bots: Vec<&Player> = self.bots.values().collect();
+    bots.sort_by(|a, b| a.name.cmp(&b.name));
+
+    players.extend(bots);
+    return players;
+  }
+
+  // Player records start with a type byte (only used from engine network version 11 / season 9 on):
+  // 3 (0x03): bot, nothing follows
+  // 16 (0x10): named bot, followed by the bot name as a string
+  // 17 (0x11): player, followed by the id length as a byte and the id itself (always 16 bytes so far)
+  // Human display names are not part of these records, they are only replicated through the player state
+  pub fn parse_player(&mut self, data: &mut Reader) -> Result<Player, ReaderError> {
+    let player_type = data.read_byte()?;
+
+    return Ok(match player_type {
       3 => Player {
         name: String::from("Bot"),
-        id: String::from(""),
+        id: PlayerId::new(""),
         is_bot: true
       },
       16 => Player {
-        name: data.read_string(),
-        id: String::from(""),
+        name: data.read_string()?,
+        id: PlayerId::new(""),
         is_bot: true
       },
+      17 => {
+        let id_length = data.read_byte()? as usize;
+        let id_bytes = data.read_bytes(&id_length)?;
+        let mut id = String::from("");
+
+        for byte in id_bytes.iter() {
+          id.push_str(&format!("{:02x}", byte));
+        }
+
+        Player {
+          name: String::from(""),
+          id: PlayerId(id),
+          is_bot: false
+        }
+      },
       _ => {
-        data.skip(&1);
+        data.skip(&1)?; // id length, read_id always reads 16 bytes
         Player {
           name: String::from(""),
-          id: data.read_id(),
+          id: PlayerId(data.read_id()?),
           is_bot: false
         }
       }
-    }
+    });
   }
 
-  pub fn parse_team_match_stats(&mut self, data: &mut Reader) -> TeamMatchStats {
-    data.skip(&4);
-    let placement = data.read_u32();
-    let total_players = data.read_u32();
+  // [unknown: u32][placement: u32][total players: u32]
+  // No team roster was seen in here in 1.replay (a solo match), so there's nothing to attribute placements to
+  // teammates with yet. Anything after total players is left unread
+  pub fn parse_team_match_stats(&mut self, data: &mut Reader) -> Result<TeamMatchStats, ReaderError> {
+    data.skip(&4)?; // unknown
+    let placement = data.read_u32()?;
+    let total_players = data.read_u32()?;
 
-    return TeamMatchStats {
+    return Ok(TeamMatchStats {
       placement,
       total_players
-    }
+    });
   }
 
-  pub fn parse_match_stats(&mut self, data: &mut Reader) -> MatchStats {
-    data.skip(&4);
-    let accuracy = data.read_f32();
-    let assists = data.read_u32();
-    let eliminations = data.read_u32();
-    let weapon_damage = data.read_u32();
-    let other_damage = data.read_u32();
-    let revives = data.read_u32();
-    let damage_taken = data.read_u32();
-    let damage_to_structures = data.read_u32();
-    let materials_gathered = data.read_u32();
-    let materials_used = data.read_u32();
-    let total_traveled = data.read_u32();
+  // [unknown: u32][accuracy: f32][assists, eliminations, weapon damage, other damage, revives, damage taken,
+  // damage to structures, materials gathered, materials used, total traveled: u32], 48 bytes in 1.replay.
+  // A per weapon damage breakdown isn't parsed, no replay we have carries one to take its layout from
+  pub fn parse_match_stats(&mut self, data: &mut Reader) -> Result<MatchStats, ReaderError> {
+    data.skip(&4)?; // unknown
+    let accuracy = data.read_f32()?;
+    let assists = data.read_u32()?;
+    let eliminations = data.read_u32()?;
+    let weapon_damage = data.read_u32()?;
+    let other_damage = data.read_u32()?;
+    let revives = data.read_u32()?;
+    let damage_taken = data.read_u32()?;
+    let damage_to_structures = data.read_u32()?;
+    let materials_gathered = data.read_u32()?;
+    let materials_used = data.read_u32()?;
+    let total_traveled = data.read_u32()?;
+    // Anything after total traveled is left unread, see above
 
-    return MatchStats {
+    return Ok(MatchStats {
       accuracy,
       assists,
       eliminations,
@@ -352,6 +1942,24 @@ impl Parser {
       materials_gathered,
       materials_used,
       total_traveled
-    }
+    });
+  }
+}
+
+impl IntoIterator for Parser {
+  type Item = Elimination;
+  type IntoIter = std::vec::IntoIter<Elimination>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    return self.into_eliminations().into_iter();
+  }
+}
+
+impl<'a> IntoIterator for &'a Parser {
+  type Item = &'a Elimination;
+  type IntoIter = std::slice::Iter<'a, Elimination>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    return self.eliminations.iter();
   }
 }
@@ -0,0 +1,9 @@
+// The types most users need, `use replay_reader::prelude::*;` brings them all into scope
+pub use crate::builder::ParserBuilder;
+pub use crate::parser::{
+  Checkpoint, ChunkInfo, ChunkStats, Elimination, EventInfo, GameVersion, Header, HeaderFlags, MatchStats, Meta, ParseError, ParseMode, Parser,
+  Player, PlayerId, PlayerSummary, RawEvent, Replay, ReplayDiff, ReplaySummary, TeamMatchStats,
+  TimelineEvent
+};
+pub use crate::reader::{CipherMode, DecryptError, Reader, ReaderError};
+pub use crate::weapons::DeathCause;
@@ -1,171 +1,614 @@
 #![allow(dead_code)]
 
-use byteorder::{ByteOrder, LittleEndian};
-use std::fs::{File, metadata};
-use std::io::Read;
-use block_modes::{BlockMode, Ecb, block_padding::ZeroPadding};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "std")]
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom};
+use block_modes::{BlockMode, Cbc, Ecb, block_padding::NoPadding};
 use aes_soft::Aes256;
 
+#[derive(Debug)]
+pub enum ReaderError {
+  OutOfBounds {
+    offset: usize,
+    length: usize,
+    buffer_len: usize
+  },
+  InvalidString {
+    offset: usize,
+    reason: &'static str
+  },
+  InvalidPackedInt {
+    offset: usize
+  },
+  InvalidBitCount {
+    count: usize,
+    max: usize
+  },
+  NotAppendable
+}
+
+impl fmt::Display for ReaderError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    return match self {
+      ReaderError::OutOfBounds { offset, length, buffer_len } => write!(f, "cannot read {} bytes at offset {}: buffer is only {} bytes long", length, offset, buffer_len),
+      ReaderError::InvalidString { offset, reason } => write!(f, "invalid string at offset {}: {}", offset, reason),
+      ReaderError::InvalidPackedInt { offset } => write!(f, "packed integer at offset {} is longer than 5 bytes", offset),
+      ReaderError::InvalidBitCount { count, max } => write!(f, "cannot read {} bits into a {} bit integer", count, max),
+      ReaderError::NotAppendable => write!(f, "only readers created from bytes can be appended to")
+    }
+  }
+}
+
+impl Error for ReaderError {}
+
+#[derive(Debug)]
+pub enum DecryptError {
+  MissingKey,
+  InvalidKey,
+  InvalidData,
+  InvalidPadding
+}
+
+impl fmt::Display for DecryptError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    return match self {
+      DecryptError::MissingKey => write!(f, "no encryption key found"),
+      DecryptError::InvalidKey => write!(f, "encryption key is not a valid AES-256 key"),
+      DecryptError::InvalidData => write!(f, "encrypted data is not a multiple of the AES block size"),
+      DecryptError::InvalidPadding => write!(f, "decrypted data has invalid padding, the encryption key is probably wrong")
+    }
+  }
+}
+
+impl Error for DecryptError {}
+
+// Lowercase hex in byte order, dashed gives the 8-4-4-4-12 UUID form
+pub fn format_guid(guid: &[u8; 16], dashed: bool) -> String {
+  let mut id = String::from("");
+
+  for (i, byte) in guid.iter().enumerate() {
+    if dashed && (i == 4 || i == 6 || i == 8 || i == 10) {
+      id.push('-');
+    }
+
+    id.push_str(&format!("{:02x}", byte));
+  }
+
+  return id;
+}
+
+// The block cipher mode encrypted data is decrypted with. Every replay seen so far uses ECB
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CipherMode {
+  #[default]
+  Ecb,
+  Cbc { iv: [u8; 16] }
+}
+
+impl CipherMode {
+  // Decrypts whole AES-256 blocks in place, the padding is left for the caller to check
+  pub fn decrypt<'a>(&self, key: &[u8], data: &'a mut [u8]) -> Result<&'a [u8], DecryptError> {
+    return match self {
+      CipherMode::Ecb => {
+        let cipher = Ecb::<Aes256, NoPadding>::new_var(key, Default::default()).map_err(|_| DecryptError::InvalidKey)?;
+        cipher.decrypt(data).map_err(|_| DecryptError::InvalidData)
+      },
+      CipherMode::Cbc { iv } => {
+        let cipher = Cbc::<Aes256, NoPadding>::new_var(key, iv).map_err(|_| DecryptError::InvalidKey)?;
+        cipher.decrypt(data).map_err(|_| DecryptError::InvalidData)
+      }
+    }
+  }
+}
+
+fn has_valid_padding(data: &[u8]) -> bool {
+  let padding_length = *data.last().unwrap() as usize;
+  if padding_length == 0 || padding_length > 16 || padding_length > data.len() {
+    return false;
+  }
+
+  return data[data.len() - padding_length..].iter().all(|byte| *byte as usize == padding_length);
+}
+
+const STREAM_WINDOW_SIZE: usize = 64 * 1024;
+
+pub trait ReadSeek: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+enum Backing {
+  Memory(Vec<u8>),
+  #[cfg(feature = "mmap")]
+  Mmap(memmap2::Mmap),
+  // only the window starting at window_start is held in memory, it's refilled from source when a read leaves it
+  Stream {
+    source: Box<dyn ReadSeek>,
+    window: Vec<u8>,
+    window_start: usize,
+    len: usize
+  }
+}
+
 pub struct Reader {
-  pub buffer: Vec<u8>,
+  backing: Backing,
   pub offset: usize,
-  pub encryption_key: Option<Vec<u8>>
+  pub encryption_key: Option<Vec<u8>>,
+  // Replaces invalid characters in strings instead of returning an error
+  pub lossy_strings: bool,
+  // Replays are little endian throughout, this is only for sub formats that aren't
+  pub big_endian: bool,
+  pub cipher_mode: CipherMode
 }
 
 impl Reader {
-  pub fn new(path: &str) -> Self {
-    let mut f = File::open(&path).expect("no file found");
-    let metadata = metadata(&path).expect("unable to read metadata");
-    let mut buffer = vec![0; metadata.len() as usize];
-    f.read(&mut buffer).expect("buffer overflow");
+  #[cfg(feature = "std")]
+  pub fn new(path: &str) -> io::Result<Self> {
+    let buffer = fs::read(path)?;
+
+    return Ok(Self::from_bytes(buffer));
+  }
 
+  // Maps the file instead of reading it, so only the pages that are read end up in memory.
+  // The file must not be changed or truncated while the reader exists, see memmap2::Mmap::map
+  #[cfg(feature = "mmap")]
+  pub fn from_mmap(path: &str) -> io::Result<Self> {
+    let file = fs::File::open(path)?;
+    let mmap = unsafe { memmap2::Mmap::map(&file)? };
+
+    return Ok(Self {
+      backing: Backing::Mmap(mmap),
+      offset: 0,
+      encryption_key: None,
+      lossy_strings: false,
+      big_endian: false,
+      cipher_mode: CipherMode::Ecb
+    });
+  }
+
+  pub fn from_bytes(buffer: Vec<u8>) -> Self {
     return Self {
-      buffer,
+      backing: Backing::Memory(buffer),
+      offset: 0,
+      encryption_key: None,
+      lossy_strings: false,
+      big_endian: false,
+      cipher_mode: CipherMode::Ecb
+    }
+  }
+
+  pub fn from_stream<R: ReadSeek + 'static>(mut source: R) -> io::Result<Self> {
+    let len = source.seek(SeekFrom::End(0))? as usize;
+    source.seek(SeekFrom::Start(0))?;
+
+    return Ok(Self {
+      backing: Backing::Stream {
+        source: Box::new(source),
+        window: vec![],
+        window_start: 0,
+        len
+      },
       offset: 0,
-      encryption_key: None
+      encryption_key: None,
+      lossy_strings: false,
+      big_endian: false,
+      cipher_mode: CipherMode::Ecb
+    });
+  }
+
+  pub fn set_encryption_key(&mut self, key: Vec<u8>) -> Result<(), DecryptError> {
+    if key.len() != 32 {
+      return Err(DecryptError::InvalidKey);
+    }
+
+    self.encryption_key = Some(key);
+    return Ok(());
+  }
+
+  // The total length of the underlying data, even if it's not loaded yet
+  pub fn len(&self) -> usize {
+    return match &self.backing {
+      Backing::Memory(buffer) => buffer.len(),
+      #[cfg(feature = "mmap")]
+      Backing::Mmap(mmap) => mmap.len(),
+      Backing::Stream { len, .. } => *len
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    return self.len() == 0;
+  }
+
+  // The bytes currently held in memory, which for streams is only the loaded window
+  pub fn buffer(&self) -> &[u8] {
+    return match &self.backing {
+      Backing::Memory(buffer) => buffer,
+      #[cfg(feature = "mmap")]
+      Backing::Mmap(mmap) => mmap,
+      Backing::Stream { window, .. } => window
+    }
+  }
+
+  pub fn into_buffer(self) -> Vec<u8> {
+    return match self.backing {
+      Backing::Memory(buffer) => buffer,
+      #[cfg(feature = "mmap")]
+      Backing::Mmap(mmap) => mmap.to_vec(),
+      Backing::Stream { window, .. } => window
+    }
+  }
+
+  // Adds bytes to the end, e.g. of a replay that is still being recorded
+  pub fn append(&mut self, bytes: &[u8]) -> Result<(), ReaderError> {
+    return match &mut self.backing {
+      Backing::Memory(buffer) => {
+        buffer.extend_from_slice(bytes);
+        Ok(())
+      },
+      _ => Err(ReaderError::NotAppendable)
     }
   }
 
-  pub fn skip(&mut self, byte_count: &usize) { // d7mn86cg
-    self.offset += *byte_count;
+  pub fn skip(&mut self, byte_count: &usize) -> Result<(), ReaderError> { // d7mn86cg
+    return match self.offset.checked_add(*byte_count) {
+      Some(byte_offset) if byte_offset <= self.len() => {
+        self.offset = byte_offset;
+        Ok(())
+      },
+      _ => Err(ReaderError::OutOfBounds {
+        offset: self.offset,
+        length: *byte_count,
+        buffer_len: self.len()
+      })
+    }
+  }
+
+  // Moves forward to the next offset that's a multiple of alignment (from the start of the buffer),
+  // nothing happens if the offset already is one. An alignment of 0 counts as 1
+  pub fn align_to(&mut self, alignment: &usize) -> Result<(), ReaderError> {
+    let alignment = (*alignment).max(1);
+    let padding = (alignment - self.offset % alignment) % alignment;
+
+    return self.skip(&padding);
   }
 
-  pub fn goto(&mut self, byte_offset: &usize) {
+  // Going to the very end is allowed, it's where is_eof() starts returning true
+  pub fn goto(&mut self, byte_offset: &usize) -> Result<(), ReaderError> {
+    if *byte_offset > self.len() {
+      return Err(ReaderError::OutOfBounds {
+        offset: *byte_offset,
+        length: 0,
+        buffer_len: self.len()
+      });
+    }
+
     self.offset = *byte_offset;
+    return Ok(());
   }
 
-  pub fn read_u16(&mut self) -> u16 {
-    let num = LittleEndian::read_u16(&self.buffer[self.offset..self.offset + 2]);
-    self.skip(&2);
-    return num;
+  pub fn remaining(&self) -> usize {
+    return self.len().saturating_sub(self.offset);
   }
 
-  pub fn read_u32(&mut self) -> u32 {
-    let num = LittleEndian::read_u32(&self.buffer[self.offset..self.offset + 4]);
-    self.skip(&4);
-    return num;
+  pub fn is_eof(&self) -> bool {
+    return self.remaining() == 0;
   }
 
-  pub fn read_u64(&mut self) -> u64 {
-    let num = LittleEndian::read_u64(&self.buffer[self.offset..self.offset + 8]);
-    self.skip(&8);
-    return num;
+  fn check_bounds(&mut self, byte_count: &usize) -> Result<(), ReaderError> {
+    let error = ReaderError::OutOfBounds {
+      offset: self.offset,
+      length: *byte_count,
+      buffer_len: self.len()
+    };
+
+    let end = match self.offset.checked_add(*byte_count) {
+      Some(end) if end <= self.len() => end,
+      _ => return Err(error)
+    };
+
+    if let Backing::Stream { source, window, window_start, len } = &mut self.backing {
+      if self.offset < *window_start || end > *window_start + window.len() {
+        let load_length = (*byte_count).max(STREAM_WINDOW_SIZE).min(*len - self.offset);
+        window.resize(load_length, 0);
+
+        let loaded = source.seek(SeekFrom::Start(self.offset as u64)).and_then(|_| source.read_exact(window));
+        if loaded.is_err() {
+          window.clear();
+          return Err(error);
+        }
+
+        *window_start = self.offset;
+      }
+    }
+
+    return Ok(());
+  }
+
+  // Callers have to run check_bounds for the same range first
+  fn loaded_bytes(&self, offset: usize, byte_count: usize) -> &[u8] {
+    return match &self.backing {
+      Backing::Memory(buffer) => &buffer[offset..offset + byte_count],
+      #[cfg(feature = "mmap")]
+      Backing::Mmap(mmap) => &mmap[offset..offset + byte_count],
+      Backing::Stream { window, window_start, .. } => &window[offset - window_start..offset - window_start + byte_count]
+    }
+  }
+
+  // Every number goes through here, so big_endian applies to all of them
+  fn read_number<T>(&mut self, byte_count: usize, little_endian: fn(&[u8]) -> T, big_endian: fn(&[u8]) -> T) -> Result<T, ReaderError> {
+    let read = if self.big_endian { big_endian } else { little_endian };
+    let num = read(self.peek_bytes(&byte_count)?);
+    self.skip(&byte_count)?;
+    return Ok(num);
+  }
+
+  pub fn read_u16(&mut self) -> Result<u16, ReaderError> {
+    return self.read_number(2, LittleEndian::read_u16, BigEndian::read_u16);
+  }
+
+  pub fn read_u32(&mut self) -> Result<u32, ReaderError> {
+    return self.read_number(4, LittleEndian::read_u32, BigEndian::read_u32);
+  }
+
+  pub fn read_u64(&mut self) -> Result<u64, ReaderError> {
+    return self.read_number(8, LittleEndian::read_u64, BigEndian::read_u64);
   }
 
-  pub fn read_i16(&mut self) -> i16 {
-    let num = LittleEndian::read_i16(&self.buffer[self.offset..self.offset + 2]);
-    self.skip(&2);
-    return num;
+  pub fn read_i16(&mut self) -> Result<i16, ReaderError> {
+    return self.read_number(2, LittleEndian::read_i16, BigEndian::read_i16);
   }
 
-  pub fn read_i32(&mut self) -> i32 {
-    let num = LittleEndian::read_i32(&self.buffer[self.offset..self.offset + 4]);
-    self.skip(&4);
-    return num;
+  pub fn read_i32(&mut self) -> Result<i32, ReaderError> {
+    return self.read_number(4, LittleEndian::read_i32, BigEndian::read_i32);
   }
 
-  pub fn read_i64(&mut self) -> i64 {
-    let num = LittleEndian::read_i64(&self.buffer[self.offset..self.offset + 8]);
-    self.skip(&8);
-    return num;
+  pub fn read_i64(&mut self) -> Result<i64, ReaderError> {
+    return self.read_number(8, LittleEndian::read_i64, BigEndian::read_i64);
   }
 
-  pub fn read_f32(&mut self) -> f32 {
-    let num = LittleEndian::read_f32(&self.buffer[self.offset..self.offset + 8]);
-    self.skip(&4);
-    return num;
+  pub fn read_f32(&mut self) -> Result<f32, ReaderError> {
+    return self.read_number(4, LittleEndian::read_f32, BigEndian::read_f32);
   }
 
-  pub fn read_byte(&mut self) -> u8 {
-    let byte = self.buffer[self.offset..self.offset + 1][0];
-    self.skip(&1);
-    return byte;
+  pub fn read_f64(&mut self) -> Result<f64, ReaderError> {
+    return self.read_number(8, LittleEndian::read_f64, BigEndian::read_f64);
   }
 
-  pub fn read_bytes(&mut self, &byte_count: &usize) -> &[u8] {
-    let bytes = &self.buffer[self.offset..self.offset + byte_count];
+  pub fn read_byte(&mut self) -> Result<u8, ReaderError> {
+    let byte = self.peek_byte()?;
+    self.skip(&1)?;
+    return Ok(byte);
+  }
+
+  pub fn read_u8(&mut self) -> Result<u8, ReaderError> {
+    return self.read_byte();
+  }
+
+  pub fn read_i8(&mut self) -> Result<i8, ReaderError> {
+    return Ok(self.read_byte()? as i8);
+  }
+
+  pub fn read_bytes(&mut self, &byte_count: &usize) -> Result<&[u8], ReaderError> {
+    self.check_bounds(&byte_count)?;
+    let start_offset = self.offset;
     self.offset += byte_count;
-    return bytes;
+    return Ok(self.loaded_bytes(start_offset, byte_count));
+  }
+
+  // For bytes that outlive the next read. read_bytes stays borrowed since most callers only copy a few bytes
+  // out of it or decode them in place
+  pub fn read_bytes_owned(&mut self, byte_count: &usize) -> Result<Vec<u8>, ReaderError> {
+    return Ok(self.read_bytes(byte_count)?.to_vec());
+  }
+
+  // Peeking takes &mut self since streamed readers may have to load the bytes first
+  pub fn peek_bytes(&mut self, &byte_count: &usize) -> Result<&[u8], ReaderError> {
+    self.check_bounds(&byte_count)?;
+    return Ok(self.loaded_bytes(self.offset, byte_count));
+  }
+
+  pub fn peek_byte(&mut self) -> Result<u8, ReaderError> {
+    return Ok(self.peek_bytes(&1)?[0]);
   }
 
-  pub fn read_bool(&mut self) -> bool {
-    return self.read_i32() == 1;
+  pub fn peek_u32(&mut self) -> Result<u32, ReaderError> {
+    let read = if self.big_endian { BigEndian::read_u32 } else { LittleEndian::read_u32 };
+    return Ok(read(self.peek_bytes(&4)?));
   }
 
-  pub fn read_id(&mut self) -> String {
-    let bytes = self.read_bytes(&16);
-    let mut id = String::from("");
+  // Same encoding as BitReader::read_int_packed, for packed ints that start on a byte boundary
+  pub fn read_packed_u32(&mut self) -> Result<u32, ReaderError> {
+    let start_offset = self.offset;
+    let mut value: u32 = 0;
 
-    for byte in bytes.iter() {
-      id.push_str(&format!("{:02X?}", byte));
+    for i in 0..5 {
+      let byte = self.read_byte()?;
+      value |= ((byte >> 1) as u32).wrapping_shl(7 * i);
+
+      if byte & 1 == 0 {
+        return Ok(value);
+      }
     }
 
-    return id.to_lowercase();
+    return Err(ReaderError::InvalidPackedInt { offset: start_offset });
+  }
+
+  pub fn read_bool(&mut self) -> Result<bool, ReaderError> {
+    return Ok(self.read_u32()? != 0);
+  }
+
+  pub fn read_bool_byte(&mut self) -> Result<bool, ReaderError> {
+    return Ok(self.read_byte()? != 0);
   }
 
-  pub fn read_string(&mut self) -> String {
-    let string_length = self.read_i32();
+  pub fn read_id(&mut self) -> Result<String, ReaderError> {
+    return Ok(format_guid(&self.read_guid()?, false));
+  }
+
+  pub fn read_guid(&mut self) -> Result<[u8; 16], ReaderError> {
+    let mut guid = [0u8; 16];
+    guid.copy_from_slice(self.read_bytes(&16)?);
+    return Ok(guid);
+  }
+
+  // Strings are prefixed with their length including the null terminator, a negative length means UTF-16
+  pub fn read_string(&mut self) -> Result<String, ReaderError> {
+    // Lengths are checked against what's left before anything is allocated, corrupt files can claim gigabytes
+    let string_offset = self.offset;
+    let string_length = self.read_i32()?;
     if string_length == 0 {
-      return String::from("");
+      return Ok(String::from(""));
     }
     else if string_length < 0 {
-      let mut u16_vec: Vec<u16> = vec![];
-      
-      for _ in 0..string_length * -1 {
-        u16_vec.push(self.read_u16());
+      let char_count = string_length.unsigned_abs() as usize;
+      self.check_bounds(&char_count.saturating_mul(2))?;
+
+      let mut u16_vec: Vec<u16> = Vec::with_capacity(char_count);
+
+      for _ in 0..char_count {
+        u16_vec.push(self.read_u16()?);
       }
 
-      u16_vec.pop();
+      self.strip_terminator(&mut u16_vec, string_offset)?;
+
+      if self.lossy_strings {
+        return Ok(String::from_utf16_lossy(&u16_vec));
+      }
 
-      return String::from_utf16(&u16_vec).expect("Cannot parse u16 vector to utf16 string");
+      return String::from_utf16(&u16_vec).map_err(|_| ReaderError::InvalidString { offset: string_offset, reason: "not valid UTF-16" });
     }
     else {
-      let bytes = self.read_bytes(&(string_length as usize));
+      let bytes = self.read_bytes(&(string_length as usize))?;
       let mut byte_vec: Vec<u8> = bytes.to_vec();
 
-      byte_vec.pop();
+      self.strip_terminator(&mut byte_vec, string_offset)?;
 
-      return String::from_utf8(byte_vec).expect("Cannot parse u8 vector to utf8 string");
+      return self.decode_utf8(byte_vec, string_offset);
     }
   }
 
-  pub fn read_string_vec(&mut self) -> Vec<String> {
-    let array_length = self.read_u32();
-    let mut vec: Vec<String> = vec![];
+  // Exactly byte_count bytes holding a UTF-8 string that ends at the first null, if there is one
+  pub fn read_string_fixed(&mut self, byte_count: &usize) -> Result<String, ReaderError> {
+    let string_offset = self.offset;
+    let bytes = self.read_bytes(byte_count)?;
+    let string_length = bytes.iter().position(|byte| *byte == 0).unwrap_or(bytes.len());
+    let byte_vec = bytes[..string_length].to_vec();
 
-    for _ in 0..array_length {
-      vec.push(self.read_string())
+    return self.decode_utf8(byte_vec, string_offset);
+  }
+
+  // A UTF-8 string running up to a null byte, the null is consumed but not part of the string
+  pub fn read_cstring(&mut self) -> Result<String, ReaderError> {
+    let string_offset = self.offset;
+    let mut byte_vec: Vec<u8> = vec![];
+
+    loop {
+      let byte = match self.read_byte() {
+        Ok(byte) => byte,
+        Err(_) => {
+          self.offset = string_offset;
+          return Err(ReaderError::InvalidString { offset: string_offset, reason: "missing null terminator" });
+        }
+      };
+
+      if byte == 0 {
+        break;
+      }
+
+      byte_vec.push(byte);
     }
 
-    return vec;
+    return self.decode_utf8(byte_vec, string_offset);
+  }
+
+  fn decode_utf8(&self, byte_vec: Vec<u8>, string_offset: usize) -> Result<String, ReaderError> {
+    if self.lossy_strings {
+      return Ok(String::from_utf8_lossy(&byte_vec).into_owned());
+    }
+
+    return String::from_utf8(byte_vec).map_err(|_| ReaderError::InvalidString { offset: string_offset, reason: "not valid UTF-8" });
+  }
+
+  // Lossy readers keep the last character if the terminator is missing, strict ones reject the string
+  fn strip_terminator<T: Default + PartialEq>(&self, units: &mut Vec<T>, string_offset: usize) -> Result<(), ReaderError> {
+    match units.last() {
+      Some(unit) if *unit == T::default() => {
+        units.pop();
+      },
+      _ if self.lossy_strings => {},
+      _ => return Err(ReaderError::InvalidString { offset: string_offset, reason: "missing null terminator" })
+    };
+
+    return Ok(());
   }
 
-  pub fn read_string_u32_tuple_vec(&mut self) -> Vec<(String, u32)> {
-    let array_length = self.read_u32();
-    let mut vec: Vec<(String, u32)> = vec![];
+  // Reads a u32 length followed by that many items
+  pub fn read_array<T, F>(&mut self, mut read_item: F) -> Result<Vec<T>, ReaderError> where F: FnMut(&mut Reader) -> Result<T, ReaderError> {
+    let array_offset = self.offset;
+    let array_length = self.read_u32()? as usize;
+
+    // Every item takes at least a byte, so longer arrays can't be valid
+    if array_length > self.remaining() {
+      return Err(ReaderError::OutOfBounds {
+        offset: array_offset,
+        length: array_length,
+        buffer_len: self.len()
+      });
+    }
+
+    let mut vec: Vec<T> = Vec::with_capacity(array_length);
 
     for _ in 0..array_length {
-      vec.push((self.read_string(), self.read_u32()));
+      vec.push(read_item(self)?);
     }
 
-    return vec;
+    return Ok(vec);
+  }
+
+  pub fn read_string_vec(&mut self) -> Result<Vec<String>, ReaderError> {
+    return self.read_array(|reader| reader.read_string());
+  }
+
+  // Ids as hex strings like read_id, e.g. for lists of account ids
+  pub fn read_id_vec(&mut self) -> Result<Vec<String>, ReaderError> {
+    return self.read_array(|reader| reader.read_id());
   }
 
-  pub fn decrypt_buffer(&mut self, data: Vec<u8>) -> Self {
+  pub fn read_guid_vec(&mut self) -> Result<Vec<[u8; 16]>, ReaderError> {
+    return self.read_array(|reader| reader.read_guid());
+  }
+
+  pub fn read_string_u32_tuple_vec(&mut self) -> Result<Vec<(String, u32)>, ReaderError> {
+    return self.read_array(|reader| Ok((reader.read_string()?, reader.read_u32()?)));
+  }
+
+  pub fn decrypt_buffer(&self, mut encrypted_data: Vec<u8>) -> Result<Self, DecryptError> {
     let raw_key = match &self.encryption_key {
       Some(key) => key,
-      None => panic!("No encryption key found")
+      None => return Err(DecryptError::MissingKey)
     };
 
-    let mut encrypted_data: Vec<u8> = (*data).to_vec();
+    // Payloads are PKCS#7 padded before encryption. ZeroPadding used to strip trailing zeros, which cut off
+    // payloads that legitimately ended in zeros. The length field of events is the padded length, so the
+    // padding itself is what tells the real length, exactly the padding byte's value is cut off.
+    // Data decrypted with the wrong key is random and almost never ends in valid padding
+    let mut decrypted_data = self.cipher_mode.decrypt(raw_key, &mut encrypted_data)?;
 
-    let decrypt = Ecb::<Aes256, ZeroPadding>::new_var(&raw_key, Default::default()).unwrap();
-    let decrypted_data = decrypt.decrypt(&mut encrypted_data).unwrap();
+    if !decrypted_data.is_empty() {
+      if !has_valid_padding(decrypted_data) {
+        return Err(DecryptError::InvalidPadding);
+      }
 
-    return Self {
-      offset: 0,
-      buffer: decrypted_data.to_vec(),
-      encryption_key: None
+      let padding_length = *decrypted_data.last().unwrap() as usize;
+      decrypted_data = &decrypted_data[..decrypted_data.len() - padding_length];
     }
+
+    let mut reader = Self::from_bytes(decrypted_data.to_vec());
+    reader.lossy_strings = self.lossy_strings;
+    reader.big_endian = self.big_endian;
+
+    return Ok(reader);
   }
 }
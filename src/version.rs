@@ -0,0 +1,36 @@
+// The version numbers the replay layout depends on, with what changed at each of them.
+// When a new season shifts offsets, this is where the boundary goes
+
+// Replay info (meta) file versions, see Parser::parse_meta
+pub const FILE_VERSION_COMPRESSION: u32 = 2; // is compressed flag
+pub const FILE_VERSION_RECORDED_TIMESTAMP: u32 = 3; // recording timestamp
+pub const FILE_VERSION_STREAM_CHUNK_TIMES: u32 = 4; // start, end and length in front of data chunks
+pub const FILE_VERSION_ENCRYPTION: u32 = 6; // is encrypted flag and key, memory size in data chunks
+pub const FILE_VERSION_CUSTOM_VERSIONS: u32 = 7; // custom versions array right after the file version
+pub const LATEST_FILE_VERSION: u32 = FILE_VERSION_CUSTOM_VERSIONS;
+
+// Header network versions, see Parser::parse_header
+pub const NETWORK_VERSION_HEADER_GUID: u32 = 12; // replay guid after the game network protocol (HISTORY_HEADER_GUID in the engine)
+
+// The elimination offsets in parse_elimination were written against replays up to this engine network version
+pub const MAX_SUPPORTED_ENGINE_NETWORK_VERSION: u32 = 16;
+
+// Elimination events, see Parser::parse_elimination.
+// From engine network version 11 on Fortnite 9.x, the players are player structs (a type byte followed by a bot
+// name or an id) after an 85 byte block. Before that they are plain id strings
+pub const ENGINE_NET_VERSION_PLAYER_STRUCT_CHANGE: u32 = 11;
+pub const MAJOR_VERSION_PLAYER_STRUCT_CHANGE: u32 = 9;
+// The 85 bytes are [event version: u32 (9 in 1.replay)][unknown: u8 (4 in 1.replay)] followed by the transforms
+// of the eliminated player and the eliminator, each [rotation: 4 x f32 quaternion][location: 3 x f32][scale: 3 x f32]
+pub const ELIMINATION_TRANSFORMS_OFFSET: usize = 5;
+pub const ELIMINATION_TRANSFORM_ROTATION_SIZE: usize = 16;
+pub const ELIMINATION_TRANSFORM_SCALE_SIZE: usize = 12;
+const ELIMINATION_TRANSFORM_SIZE: usize = ELIMINATION_TRANSFORM_ROTATION_SIZE + 12 + ELIMINATION_TRANSFORM_SCALE_SIZE;
+const _: () = assert!(ELIMINATION_TRANSFORMS_OFFSET + 2 * ELIMINATION_TRANSFORM_SIZE == 85);
+
+// The data in front of the id strings grew with 4.2 (from 12 to 40 bytes) and again after it (to 45 bytes)
+pub const MAJOR_VERSION_ELIMINATION_OFFSET_CHANGE: u32 = 4;
+pub const MINOR_VERSION_ELIMINATION_OFFSET_CHANGE: u32 = 2;
+pub const ELIMINATION_ID_OFFSET_BEFORE_4_2: usize = 12;
+pub const ELIMINATION_ID_OFFSET_4_2: usize = 40;
+pub const ELIMINATION_ID_OFFSET: usize = 45;
@@ -0,0 +1,260 @@
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// Mirrors the game's EDeathCause enum, which is what the gun type byte of an elimination event holds
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DeathCause {
+  OutsideSafeZone,
+  FallDamage,
+  Pistol,
+  Shotgun,
+  Rifle,
+  Smg,
+  Sniper,
+  SniperNoScope,
+  Melee,
+  InfinityBlade,
+  Grenade,
+  C4,
+  GrenadeLauncher,
+  RocketLauncher,
+  Minigun,
+  Bow,
+  Trap,
+  DbnoTimeout,
+  Banhammer,
+  RemovedFromGame,
+  MassiveMelee,
+  MassiveDiveBomb,
+  MassiveRanged,
+  Vehicle,
+  ShoppingCart,
+  Atk,
+  QuadCrasher,
+  Biplane,
+  BiplaneGun,
+  Lmg,
+  GasGrenade,
+  InstantEnvironmental,
+  Turret,
+  Cube,
+  Balloon,
+  StormSurge,
+  Lava,
+  BasicFiend,
+  EliteFiend,
+  RangedFiend,
+  BasicBrute,
+  EliteBrute,
+  MegaBrute,
+  SilentRemoval,
+  LoggedOut,
+  TeamSwitchSuicide,
+  WonMatch,
+  Unspecified,
+  Unknown(u8)
+}
+
+impl DeathCause {
+  pub fn from_byte(byte: u8) -> Self {
+    return match byte {
+      0 => DeathCause::OutsideSafeZone,
+      1 => DeathCause::FallDamage,
+      2 => DeathCause::Pistol,
+      3 => DeathCause::Shotgun,
+      4 => DeathCause::Rifle,
+      5 => DeathCause::Smg,
+      6 => DeathCause::Sniper,
+      7 => DeathCause::SniperNoScope,
+      8 => DeathCause::Melee,
+      9 => DeathCause::InfinityBlade,
+      10 => DeathCause::Grenade,
+      11 => DeathCause::C4,
+      12 => DeathCause::GrenadeLauncher,
+      13 => DeathCause::RocketLauncher,
+      14 => DeathCause::Minigun,
+      15 => DeathCause::Bow,
+      16 => DeathCause::Trap,
+      17 => DeathCause::DbnoTimeout,
+      18 => DeathCause::Banhammer,
+      19 => DeathCause::RemovedFromGame,
+      20 => DeathCause::MassiveMelee,
+      21 => DeathCause::MassiveDiveBomb,
+      22 => DeathCause::MassiveRanged,
+      23 => DeathCause::Vehicle,
+      24 => DeathCause::ShoppingCart,
+      25 => DeathCause::Atk,
+      26 => DeathCause::QuadCrasher,
+      27 => DeathCause::Biplane,
+      28 => DeathCause::BiplaneGun,
+      29 => DeathCause::Lmg,
+      30 => DeathCause::GasGrenade,
+      31 => DeathCause::InstantEnvironmental,
+      32 => DeathCause::Turret,
+      33 => DeathCause::Cube,
+      34 => DeathCause::Balloon,
+      35 => DeathCause::StormSurge,
+      36 => DeathCause::Lava,
+      37 => DeathCause::BasicFiend,
+      38 => DeathCause::EliteFiend,
+      39 => DeathCause::RangedFiend,
+      40 => DeathCause::BasicBrute,
+      41 => DeathCause::EliteBrute,
+      42 => DeathCause::MegaBrute,
+      43 => DeathCause::SilentRemoval,
+      44 => DeathCause::LoggedOut,
+      45 => DeathCause::TeamSwitchSuicide,
+      46 => DeathCause::WonMatch,
+      47 => DeathCause::Unspecified,
+      _ => DeathCause::Unknown(byte)
+    }
+  }
+
+  // The byte from_byte maps to this death cause
+  pub fn to_byte(&self) -> u8 {
+    if let DeathCause::Unknown(byte) = self {
+      return *byte;
+    }
+
+    return (0..=LAST_KNOWN_BYTE).find(|byte| DeathCause::from_byte(*byte) == *self).unwrap_or(u8::MAX);
+  }
+
+  // The variant name, None for unknown bytes
+  pub fn name(&self) -> Option<&'static str> {
+    return match self {
+      DeathCause::OutsideSafeZone => Some("OutsideSafeZone"),
+      DeathCause::FallDamage => Some("FallDamage"),
+      DeathCause::Pistol => Some("Pistol"),
+      DeathCause::Shotgun => Some("Shotgun"),
+      DeathCause::Rifle => Some("Rifle"),
+      DeathCause::Smg => Some("Smg"),
+      DeathCause::Sniper => Some("Sniper"),
+      DeathCause::SniperNoScope => Some("SniperNoScope"),
+      DeathCause::Melee => Some("Melee"),
+      DeathCause::InfinityBlade => Some("InfinityBlade"),
+      DeathCause::Grenade => Some("Grenade"),
+      DeathCause::C4 => Some("C4"),
+      DeathCause::GrenadeLauncher => Some("GrenadeLauncher"),
+      DeathCause::RocketLauncher => Some("RocketLauncher"),
+      DeathCause::Minigun => Some("Minigun"),
+      DeathCause::Bow => Some("Bow"),
+      DeathCause::Trap => Some("Trap"),
+      DeathCause::DbnoTimeout => Some("DbnoTimeout"),
+      DeathCause::Banhammer => Some("Banhammer"),
+      DeathCause::RemovedFromGame => Some("RemovedFromGame"),
+      DeathCause::MassiveMelee => Some("MassiveMelee"),
+      DeathCause::MassiveDiveBomb => Some("MassiveDiveBomb"),
+      DeathCause::MassiveRanged => Some("MassiveRanged"),
+      DeathCause::Vehicle => Some("Vehicle"),
+      DeathCause::ShoppingCart => Some("ShoppingCart"),
+      DeathCause::Atk => Some("Atk"),
+      DeathCause::QuadCrasher => Some("QuadCrasher"),
+      DeathCause::Biplane => Some("Biplane"),
+      DeathCause::BiplaneGun => Some("BiplaneGun"),
+      DeathCause::Lmg => Some("Lmg"),
+      DeathCause::GasGrenade => Some("GasGrenade"),
+      DeathCause::InstantEnvironmental => Some("InstantEnvironmental"),
+      DeathCause::Turret => Some("Turret"),
+      DeathCause::Cube => Some("Cube"),
+      DeathCause::Balloon => Some("Balloon"),
+      DeathCause::StormSurge => Some("StormSurge"),
+      DeathCause::Lava => Some("Lava"),
+      DeathCause::BasicFiend => Some("BasicFiend"),
+      DeathCause::EliteFiend => Some("EliteFiend"),
+      DeathCause::RangedFiend => Some("RangedFiend"),
+      DeathCause::BasicBrute => Some("BasicBrute"),
+      DeathCause::EliteBrute => Some("EliteBrute"),
+      DeathCause::MegaBrute => Some("MegaBrute"),
+      DeathCause::SilentRemoval => Some("SilentRemoval"),
+      DeathCause::LoggedOut => Some("LoggedOut"),
+      DeathCause::TeamSwitchSuicide => Some("TeamSwitchSuicide"),
+      DeathCause::WonMatch => Some("WonMatch"),
+      DeathCause::Unspecified => Some("Unspecified"),
+      DeathCause::Unknown(_) => None
+    }
+  }
+}
+
+const LAST_KNOWN_BYTE: u8 = 47;
+
+// Known death causes are written as their name, unknown ones as the byte
+impl fmt::Display for DeathCause {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    return match self.name() {
+      Some(name) => write!(f, "{}", name),
+      None => write!(f, "{}", self.to_byte())
+    };
+  }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseDeathCauseError(pub String);
+
+impl fmt::Display for ParseDeathCauseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    return write!(f, "{} is neither a death cause name nor a byte", self.0);
+  }
+}
+
+impl Error for ParseDeathCauseError {}
+
+// Accepts everything Display writes, i.e. the variant names and bytes
+impl FromStr for DeathCause {
+  type Err = ParseDeathCauseError;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    if let Ok(byte) = value.parse::<u8>() {
+      return Ok(DeathCause::from_byte(byte));
+    }
+
+    return (0..=LAST_KNOWN_BYTE)
+      .map(DeathCause::from_byte)
+      .find(|death_cause| death_cause.name() == Some(value))
+      .ok_or_else(|| ParseDeathCauseError(String::from(value)));
+  }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for DeathCause {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    return match self.name() {
+      Some(name) => serializer.serialize_str(name),
+      None => serializer.serialize_u8(self.to_byte())
+    };
+  }
+}
+
+#[cfg(feature = "serde")]
+struct DeathCauseVisitor;
+
+#[cfg(feature = "serde")]
+impl de::Visitor<'_> for DeathCauseVisitor {
+  type Value = DeathCause;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    return write!(f, "a death cause name or byte");
+  }
+
+  fn visit_str<E: de::Error>(self, value: &str) -> Result<DeathCause, E> {
+    return value.parse().map_err(E::custom);
+  }
+
+  fn visit_u64<E: de::Error>(self, value: u64) -> Result<DeathCause, E> {
+    return match u8::try_from(value) {
+      Ok(byte) => Ok(DeathCause::from_byte(byte)),
+      Err(_) => Err(E::invalid_value(de::Unexpected::Unsigned(value), &self))
+    };
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for DeathCause {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    return deserializer.deserialize_any(DeathCauseVisitor);
+  }
+}
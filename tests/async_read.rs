@@ -0,0 +1,29 @@
+#![cfg(feature = "tokio")]
+#![allow(clippy::needless_return)]
+
+use replay_reader::parser::Parser;
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay");
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+  return tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(future);
+}
+
+#[test]
+fn parses_replays_read_asynchronously() {
+  let buffer = std::fs::read(FIXTURE).unwrap();
+
+  let mut parser = block_on(Parser::from_async_read(&buffer[..])).unwrap();
+  parser.parse().unwrap();
+
+  assert_eq!(parser.reader.len(), buffer.len());
+  assert_eq!(parser.eliminations.len(), 188);
+}
+
+#[test]
+fn loads_replay_files_asynchronously() {
+  let parser = block_on(Parser::from_path_async(FIXTURE)).unwrap();
+
+  assert_eq!(parser.reader.len(), std::fs::metadata(FIXTURE).unwrap().len() as usize);
+  assert!(block_on(Parser::from_path_async("/this/file/does/not/exist.replay")).is_err());
+}
@@ -0,0 +1,102 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::bit_reader::BitReader;
+use replay_reader::reader::Reader;
+
+#[test]
+fn reads_bits_lowest_first() {
+  let buffer = [0b1010_1101u8];
+  let mut bits = BitReader::new(&buffer);
+
+  assert!(bits.read_bit().unwrap());
+  assert!(!bits.read_bit().unwrap());
+  assert!(bits.read_bit().unwrap());
+  assert_eq!(bits.read_bits_u32(5).unwrap(), 0b10101);
+  assert!(bits.is_eof());
+  assert!(bits.read_bit().is_err());
+}
+
+#[test]
+fn reads_unaligned_integers() {
+  // 0x12345678 shifted up by one bit, with a set bit in front of it
+  let value: u64 = (0x12345678u64 << 1) | 1;
+  let buffer = value.to_le_bytes();
+  let mut bits = BitReader::new(&buffer[..5]);
+
+  assert!(bits.read_bit().unwrap());
+  assert_eq!(bits.read_u32().unwrap(), 0x12345678);
+  assert_eq!(bits.remaining_bits(), 7);
+}
+
+#[test]
+fn reads_unaligned_bytes() {
+  let buffer = [0b1111_0000u8, 0b0000_1111];
+  let mut bits = BitReader::new(&buffer);
+
+  bits.skip_bits(4).unwrap();
+  assert_eq!(bits.read_bytes(1).unwrap(), vec![0xFF]);
+  assert_eq!(bits.read_bits(4).unwrap(), vec![0]);
+}
+
+#[test]
+fn respects_the_bit_count() {
+  let buffer = [0xFFu8, 0xFF];
+  let mut bits = BitReader::with_bit_count(&buffer, 10);
+
+  assert_eq!(bits.read_bits_u32(10).unwrap(), 0x3FF);
+  assert!(bits.read_bit().is_err());
+}
+
+#[test]
+fn reads_serialized_ints_with_as_few_bits_as_max_needs() {
+  // max 8 needs 3 bits, max 5 stops once the next bit would reach it
+  let buffer = [0b0011_1110u8];
+  let mut bits = BitReader::new(&buffer);
+
+  assert_eq!(bits.read_serialized_int(8).unwrap(), 6);
+  assert_eq!(bits.position, 3);
+  assert_eq!(bits.read_serialized_int(5).unwrap(), 3);
+  assert_eq!(bits.position, 5);
+}
+
+#[test]
+fn reads_packed_ints() {
+  // 300 = 0b10_0101100: (44 << 1) | 1, then (2 << 1)
+  let buffer = [10u8, 89, 4];
+  let mut bits = BitReader::new(&buffer);
+
+  assert_eq!(bits.read_int_packed().unwrap(), 5);
+  assert_eq!(bits.read_int_packed().unwrap(), 300);
+
+  let mut reader = Reader::from_bytes(buffer.to_vec());
+  assert_eq!(reader.read_packed_u32().unwrap(), 5);
+  assert_eq!(reader.read_packed_u32().unwrap(), 300);
+}
+
+#[test]
+fn reads_unaligned_packed_ints() {
+  // [89, 4] shifted up by three bits
+  let buffer = [89u8 << 3, (89 >> 5) | (4 << 3), 0];
+  let mut bits = BitReader::new(&buffer);
+
+  bits.skip_bits(3).unwrap();
+  assert_eq!(bits.read_int_packed().unwrap(), 300);
+}
+
+#[test]
+fn rejects_overlong_packed_ints() {
+  let buffer = [0xFFu8; 6];
+
+  assert!(BitReader::new(&buffer).read_int_packed().is_err());
+  assert!(Reader::from_bytes(buffer.to_vec()).read_packed_u32().is_err());
+}
+
+#[test]
+fn rejects_too_many_bits_for_the_integer() {
+  let buffer = [0u8; 16];
+  let mut bits = BitReader::new(&buffer);
+
+  assert!(bits.read_bits_u32(33).is_err());
+  assert!(bits.read_bits_u64(65).is_err());
+  assert_eq!(bits.position, 0);
+}
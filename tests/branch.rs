@@ -0,0 +1,29 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::parser::parse_branch_version;
+
+#[test]
+fn reads_major_and_minor_from_release_branches() {
+  assert_eq!(parse_branch_version("++Fortnite+Release-13.40"), (13, 40));
+  assert_eq!(parse_branch_version("++Fortnite+Release-4.1"), (4, 1));
+  assert_eq!(parse_branch_version("++Fortnite+Release-10.00-CL-9999"), (10, 0));
+  assert_eq!(parse_branch_version("Staging++Fortnite+Release-7.30"), (7, 30));
+}
+
+#[test]
+fn other_branches_are_0_0() {
+  assert_eq!(parse_branch_version("++Fortnite+Main"), (0, 0));
+  assert_eq!(parse_branch_version("++Fortnite+Release-"), (0, 0));
+  assert_eq!(parse_branch_version("++Fortnite+Release-13"), (0, 0));
+  assert_eq!(parse_branch_version("++Fortnite+Release-.40"), (0, 0));
+  assert_eq!(parse_branch_version(""), (0, 0));
+}
+
+#[test]
+fn edge_cases_match_the_old_pattern() {
+  // An empty minor and numbers too large for a u32 were 0 before as well
+  assert_eq!(parse_branch_version("++Fortnite+Release-13."), (13, 0));
+  assert_eq!(parse_branch_version("++Fortnite+Release-99999999999.1"), (0, 1));
+  // The first occurrence that has a version is used
+  assert_eq!(parse_branch_version("++Fortnite+Release-Test++Fortnite+Release-8.51"), (8, 51));
+}
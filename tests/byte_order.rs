@@ -0,0 +1,39 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::reader::Reader;
+
+#[test]
+fn reads_little_endian_by_default() {
+  let mut reader = Reader::from_bytes(vec![0x01, 0x02, 0x03, 0x04]);
+
+  assert_eq!(reader.read_u32().unwrap(), 0x04030201);
+}
+
+#[test]
+fn reads_every_number_big_endian() {
+  let mut buffer = vec![];
+  buffer.extend_from_slice(&0x0102u16.to_be_bytes());
+  buffer.extend_from_slice(&(-2i32).to_be_bytes());
+  buffer.extend_from_slice(&0x0102030405060708u64.to_be_bytes());
+  buffer.extend_from_slice(&1.5f32.to_be_bytes());
+  buffer.extend_from_slice(&(-0.25f64).to_be_bytes());
+  let mut reader = Reader::from_bytes(buffer);
+  reader.big_endian = true;
+
+  assert_eq!(reader.read_u16().unwrap(), 0x0102);
+  assert_eq!(reader.read_i32().unwrap(), -2);
+  assert_eq!(reader.read_u64().unwrap(), 0x0102030405060708);
+  assert_eq!(reader.read_f32().unwrap(), 1.5);
+  assert_eq!(reader.read_f64().unwrap(), -0.25);
+  assert!(reader.is_eof());
+}
+
+#[test]
+fn big_endian_string_lengths() {
+  let mut buffer = 3i32.to_be_bytes().to_vec();
+  buffer.extend_from_slice(b"ab\0");
+  let mut reader = Reader::from_bytes(buffer);
+  reader.big_endian = true;
+
+  assert_eq!(reader.read_string().unwrap(), "ab");
+}
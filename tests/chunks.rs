@@ -0,0 +1,184 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::parser::{ChunkInfo, ParseError, Parser};
+
+#[test]
+fn finds_and_reads_the_header_chunk_without_parsing() {
+  let mut parser = Parser::from_bytes(std::fs::read("1.replay").unwrap());
+
+  let info = parser.find_chunk(0).unwrap().unwrap();
+  assert_eq!(info.offset, 594);
+  assert_eq!(info.offset + 8 + info.size as usize, 756);
+  assert!(parser.header.is_none());
+  assert!(parser.chunks.is_empty());
+
+  let mut chunk = parser.read_chunk(&info).unwrap();
+  assert_eq!(chunk.len(), info.size as usize);
+  assert_eq!(chunk.read_u32().unwrap(), replay_reader::parser::HEADER_MAGIC);
+
+  // The offset is left alone, so a full parse still works afterwards
+  parser.parse().unwrap();
+  assert_eq!(parser.header.as_ref().unwrap().engine_network_version, 16);
+}
+
+#[test]
+fn finds_the_same_chunks_as_a_full_parse() {
+  let mut full = Parser::from_bytes(std::fs::read("1.replay").unwrap());
+  full.parse().unwrap();
+  let mut parser = Parser::from_bytes(std::fs::read("1.replay").unwrap());
+
+  for chunk_type in 0..=3 {
+    let expected = full.chunks.iter().find(|chunk| chunk.chunk_type == chunk_type).unwrap();
+    let found = parser.find_chunk(chunk_type).unwrap().unwrap();
+
+    assert_eq!(found.offset, expected.offset);
+    assert_eq!(found.size, expected.size);
+  }
+
+  assert!(parser.find_chunk(4).unwrap().is_none());
+}
+
+#[test]
+fn decrypts_event_payloads_from_a_chunk() {
+  let mut parser = Parser::from_bytes(std::fs::read("1.replay").unwrap());
+  let info = parser.find_chunk(3).unwrap().unwrap();
+  let mut chunk = parser.read_chunk(&info).unwrap();
+
+  let _id = chunk.read_string().unwrap();
+  let _group = chunk.read_string().unwrap();
+  let _metadata = chunk.read_string().unwrap();
+  chunk.skip(&8).unwrap();
+  let length = chunk.read_u32().unwrap() as usize;
+  let payload = chunk.read_bytes(&length).unwrap().to_vec();
+
+  assert!(chunk.decrypt_buffer(payload).is_ok());
+}
+
+#[test]
+fn adds_up_the_bytes_per_chunk_type() {
+  let mut parser = Parser::from_bytes(std::fs::read("1.replay").unwrap());
+  parser.parse().unwrap();
+  let stats = parser.chunk_stats;
+
+  assert_eq!(stats.header_bytes, 154);
+  assert_eq!(stats.data_bytes, 7023632);
+  assert_eq!(stats.checkpoint_bytes, 5764777);
+  assert_eq!(stats.event_bytes, 46374);
+
+  let total: u64 = parser.chunks.iter().map(|chunk| chunk.size as u64).sum();
+  assert_eq!(stats.header_bytes + stats.data_bytes + stats.checkpoint_bytes + stats.event_bytes, total);
+}
+
+#[test]
+fn a_full_parse_consumes_the_whole_file() {
+  let mut parser = Parser::from_bytes(std::fs::read("1.replay").unwrap());
+  parser.parse().unwrap();
+
+  assert!(parser.fully_consumed());
+  assert_eq!(parser.leftover_bytes(), 0);
+}
+
+#[test]
+fn stopping_early_leaves_bytes() {
+  let buffer = std::fs::read("1.replay").unwrap();
+  let buffer_len = buffer.len();
+  let mut parser = Parser::builder().meta_only(true).build_from_bytes(buffer).unwrap();
+  parser.parse().unwrap();
+
+  assert!(!parser.fully_consumed());
+  assert_eq!(parser.leftover_bytes(), buffer_len - 594);
+}
+
+// The meta and the header chunk of the fixture, followed by one more chunk header
+fn with_chunk_header(chunk_type: u32, chunk_size: i32, payload: &[u8]) -> Vec<u8> {
+  let mut buffer = std::fs::read("1.replay").unwrap()[..756].to_vec();
+  buffer.extend_from_slice(&chunk_type.to_le_bytes());
+  buffer.extend_from_slice(&chunk_size.to_le_bytes());
+  buffer.extend_from_slice(payload);
+
+  return buffer;
+}
+
+#[test]
+fn negative_chunk_sizes_are_an_error() {
+  for chunk_size in [-1, -8, i32::MIN] {
+    let mut parser = Parser::from_bytes(with_chunk_header(3, chunk_size, &[0; 16]));
+
+    match parser.parse() {
+      Err(ParseError::InvalidChunkSize { offset, size, .. }) => {
+        assert_eq!(offset, 764);
+        assert_eq!(size, chunk_size);
+      },
+      result => panic!("chunk size {}: {:?}", chunk_size, result)
+    }
+  }
+}
+
+#[test]
+fn chunk_sizes_past_the_end_are_an_error() {
+  for chunk_size in [17, 1 << 20, i32::MAX] {
+    let mut parser = Parser::from_bytes(with_chunk_header(2, chunk_size, &[0; 16]));
+
+    assert!(matches!(parser.parse(), Err(ParseError::InvalidChunkSize { .. })), "chunk size {}", chunk_size);
+  }
+}
+
+#[test]
+fn truncated_chunk_headers_are_an_error() {
+  let full = with_chunk_header(3, 0, &[]);
+
+  for length in 757..full.len() {
+    let mut parser = Parser::from_bytes(full[..length].to_vec());
+
+    assert!(matches!(parser.parse(), Err(ParseError::Reader(_))), "{} bytes", length);
+  }
+}
+
+#[test]
+fn random_chunk_headers_never_panic() {
+  // xorshift, so every run sees the same headers
+  let mut state: u32 = 0x9E3779B9;
+  let mut next = || {
+    state ^= state << 13;
+    state ^= state >> 17;
+    state ^= state << 5;
+    return state;
+  };
+
+  for _ in 0..500 {
+    let chunk_type = next() % 5;
+    let chunk_size = match next() % 3 {
+      0 => next() as i32,
+      1 => (next() % 64) as i32 - 16,
+      _ => (next() % 48) as i32
+    };
+    let payload: Vec<u8> = (0..next() % 48).map(|_| next() as u8).collect();
+
+    let mut parser = Parser::from_bytes(with_chunk_header(chunk_type, chunk_size, &payload));
+    let result = parser.parse();
+
+    if chunk_size < 0 || chunk_size as usize > payload.len() {
+      assert!(matches!(result, Err(ParseError::InvalidChunkSize { .. })), "type {}, size {}", chunk_type, chunk_size);
+    }
+  }
+}
+
+#[test]
+fn chunk_readers_keep_the_byte_order() {
+  let mut payload = 3i32.to_be_bytes().to_vec();
+  payload.extend_from_slice(b"ab\0");
+  payload.extend_from_slice(&0x01020304u32.to_be_bytes());
+  let mut buffer = 3u32.to_be_bytes().to_vec();
+  buffer.extend_from_slice(&(payload.len() as i32).to_be_bytes());
+  buffer.extend_from_slice(&payload);
+
+  let mut parser = Parser::from_bytes(buffer);
+  parser.reader.big_endian = true;
+  let info = ChunkInfo { chunk_type: 3, size: payload.len() as u32, offset: 0 };
+
+  let mut chunk = parser.read_chunk(&info).unwrap();
+  assert!(chunk.big_endian);
+  assert_eq!(chunk.read_string().unwrap(), "ab");
+  assert_eq!(chunk.read_u32().unwrap(), 0x01020304);
+  assert!(chunk.is_eof());
+}
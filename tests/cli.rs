@@ -0,0 +1,27 @@
+#![cfg(feature = "std")]
+#![allow(clippy::needless_return)]
+
+mod common;
+
+use common::{player_id_string, FixtureElimination, FixturePlayer, ReplayFixture};
+use std::process::Command;
+
+#[test]
+fn csv_writes_unknown_death_causes_as_the_byte() {
+  let mut fixture = ReplayFixture::new(6, true, "++Fortnite+Release-13.40");
+  fixture.eliminations = vec![
+    FixtureElimination { eliminated: FixturePlayer::Human(1), eliminator: FixturePlayer::Human(2), gun_type: 200, knocked: false, time: 61000 },
+    FixtureElimination { eliminated: FixturePlayer::Human(3), eliminator: FixturePlayer::Human(2), gun_type: 3, knocked: true, time: 62000 }
+  ];
+  let path = std::env::temp_dir().join(format!("replay-reader-cli-{}.replay", std::process::id()));
+  std::fs::write(&path, fixture.build()).unwrap();
+
+  let output = Command::new(env!("CARGO_BIN_EXE_replay-reader")).arg(&path).args(["--format", "csv"]).output().unwrap();
+  std::fs::remove_file(&path).unwrap();
+
+  let csv = String::from_utf8(output.stdout).unwrap();
+  let lines: Vec<&str> = csv.lines().collect();
+  assert_eq!(lines.len(), 3);
+  assert_eq!(lines[1], format!("61000,{},,{},,200,false", player_id_string(2), player_id_string(1)));
+  assert_eq!(lines[2], format!("62000,{},,{},,Shotgun,true", player_id_string(2), player_id_string(3)));
+}
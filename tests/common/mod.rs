@@ -0,0 +1,283 @@
+// Builds small replays in memory for the tests, shaped like the engine writes them.
+// Every id is made up, nothing in here comes from a real player
+#![allow(dead_code)]
+
+use block_modes::block_padding::Pkcs7;
+use block_modes::{BlockMode, Ecb};
+use aes_soft::Aes256;
+use replay_reader::parser::{HEADER_MAGIC, REPLAY_MAGIC};
+use replay_reader::version::NETWORK_VERSION_HEADER_GUID;
+
+pub const KEY: [u8; 32] = [0x5A; 32];
+
+pub fn push_string(buffer: &mut Vec<u8>, value: &str) {
+  buffer.extend_from_slice(&(value.len() as i32 + 1).to_le_bytes());
+  buffer.extend_from_slice(value.as_bytes());
+  buffer.push(0);
+}
+
+pub fn push_chunk(buffer: &mut Vec<u8>, chunk_type: u32, data: &[u8]) {
+  buffer.extend_from_slice(&chunk_type.to_le_bytes());
+  buffer.extend_from_slice(&(data.len() as i32).to_le_bytes());
+  buffer.extend_from_slice(data);
+}
+
+// A made up 16 byte id, the same seed always gives the same id
+pub fn player_id(seed: u8) -> [u8; 16] {
+  let mut id = [0u8; 16];
+  for (i, byte) in id.iter_mut().enumerate() {
+    *byte = seed.wrapping_mul(31).wrapping_add(i as u8);
+  }
+
+  return id;
+}
+
+pub fn player_id_string(seed: u8) -> String {
+  return player_id(seed).iter().map(|byte| format!("{:02x}", byte)).collect();
+}
+
+pub enum FixturePlayer {
+  Human(u8),
+  NamedBot(&'static str),
+  Bot,
+  // Written as is, e.g. to break the event
+  Raw(&'static [u8])
+}
+
+pub struct FixtureElimination {
+  pub eliminated: FixturePlayer,
+  pub eliminator: FixturePlayer,
+  pub gun_type: u8,
+  pub knocked: bool,
+  pub time: u32
+}
+
+pub struct ReplayFixture {
+  pub file_version: u32,
+  pub encrypted: bool,
+  pub network_version: u32,
+  pub engine_network_version: u32,
+  pub branch: &'static str,
+  pub length_in_ms: u32,
+  pub is_live: bool,
+  pub compressed: bool, // only sets the flag, the data chunk and checkpoint are written as given
+  pub eliminations: Vec<FixtureElimination>,
+  pub match_stats: Option<[u32; 10]>, // assists, eliminations, ... total traveled, as in MatchStats
+  pub team_stats: Option<(u32, u32)>, // placement, total players
+  pub data_chunk: Option<Vec<u8>>,
+  pub checkpoint: Option<Vec<u8>>
+}
+
+impl ReplayFixture {
+  pub fn new(file_version: u32, encrypted: bool, branch: &'static str) -> Self {
+    return Self {
+      file_version,
+      encrypted,
+      network_version: 14,
+      engine_network_version: 16,
+      branch,
+      length_in_ms: 600000,
+      is_live: false,
+      compressed: false,
+      eliminations: vec![],
+      match_stats: None,
+      team_stats: None,
+      data_chunk: None,
+      checkpoint: None
+    };
+  }
+
+  // (major, minor) from a branch like ++Fortnite+Release-13.40
+  fn version(&self) -> (u32, u32) {
+    let version = self.branch.rsplit('-').next().unwrap_or("");
+    let mut parts = version.split('.').map(|part| part.parse().unwrap_or(0));
+    return (parts.next().unwrap_or(0), parts.next().unwrap_or(0));
+  }
+
+  fn has_player_structs(&self) -> bool {
+    return self.engine_network_version >= 11 && self.version().0 >= 9;
+  }
+
+  fn payload(&self, data: Vec<u8>) -> Vec<u8> {
+    if !self.encrypted {
+      return data;
+    }
+
+    let cipher = Ecb::<Aes256, Pkcs7>::new_var(&KEY, Default::default()).unwrap();
+    return cipher.encrypt_vec(&data);
+  }
+
+  fn meta(&self) -> Vec<u8> {
+    let mut buffer: Vec<u8> = vec![];
+
+    buffer.extend_from_slice(&REPLAY_MAGIC.to_le_bytes());
+    buffer.extend_from_slice(&self.file_version.to_le_bytes());
+    if self.file_version >= 7 {
+      buffer.extend_from_slice(&0u32.to_le_bytes());
+    }
+    buffer.extend_from_slice(&self.length_in_ms.to_le_bytes());
+    buffer.extend_from_slice(&2u32.to_le_bytes());
+    buffer.extend_from_slice(&14036559u32.to_le_bytes());
+    push_string(&mut buffer, "Unsaved Replay");
+    buffer.extend_from_slice(&(self.is_live as u32).to_le_bytes());
+    if self.file_version >= 3 {
+      buffer.extend_from_slice(&637323461365600000u64.to_le_bytes());
+    }
+    if self.file_version >= 2 {
+      buffer.extend_from_slice(&(self.compressed as u32).to_le_bytes());
+    }
+    if self.file_version >= 6 {
+      buffer.extend_from_slice(&(self.encrypted as u32).to_le_bytes());
+      if self.encrypted {
+        buffer.extend_from_slice(&32u32.to_le_bytes());
+        buffer.extend_from_slice(&KEY);
+      }
+      else {
+        buffer.extend_from_slice(&0u32.to_le_bytes());
+      }
+    }
+
+    return buffer;
+  }
+
+  fn header(&self) -> Vec<u8> {
+    let mut buffer: Vec<u8> = vec![];
+
+    buffer.extend_from_slice(&HEADER_MAGIC.to_le_bytes());
+    buffer.extend_from_slice(&self.network_version.to_le_bytes());
+    buffer.extend_from_slice(&0x12345678u32.to_le_bytes());
+    buffer.extend_from_slice(&self.engine_network_version.to_le_bytes());
+    buffer.extend_from_slice(&0u32.to_le_bytes());
+    if self.network_version >= NETWORK_VERSION_HEADER_GUID {
+      buffer.extend_from_slice(&player_id(200));
+    }
+    buffer.extend_from_slice(&0u32.to_le_bytes());
+    buffer.extend_from_slice(&0u16.to_le_bytes());
+    buffer.extend_from_slice(&14036559u32.to_le_bytes());
+    push_string(&mut buffer, self.branch);
+    buffer.extend_from_slice(&1u32.to_le_bytes());
+    push_string(&mut buffer, "/Game/Athena/Apollo/Maps/Apollo_Terrain");
+    buffer.extend_from_slice(&0u32.to_le_bytes());
+    buffer.extend_from_slice(&3u32.to_le_bytes());
+    buffer.extend_from_slice(&1u32.to_le_bytes());
+    push_string(&mut buffer, "SubGame=Athena");
+
+    return buffer;
+  }
+
+  fn push_player(&self, buffer: &mut Vec<u8>, player: &FixturePlayer) {
+    if !self.has_player_structs() {
+      let id = match player {
+        FixturePlayer::Human(seed) => player_id_string(*seed),
+        _ => String::from("")
+      };
+      if let FixturePlayer::Raw(bytes) = player {
+        buffer.extend_from_slice(bytes);
+        return;
+      }
+      push_string(buffer, &id);
+      return;
+    }
+
+    match player {
+      FixturePlayer::Human(seed) => {
+        buffer.extend_from_slice(&[17, 16]);
+        buffer.extend_from_slice(&player_id(*seed));
+      },
+      FixturePlayer::NamedBot(name) => {
+        buffer.push(16);
+        push_string(buffer, name);
+      },
+      FixturePlayer::Bot => buffer.push(3),
+      FixturePlayer::Raw(bytes) => buffer.extend_from_slice(bytes)
+    }
+  }
+
+  fn elimination(&self, elim: &FixtureElimination) -> Vec<u8> {
+    let offset = match self.version() {
+      _ if self.has_player_structs() => 85,
+      (major, minor) if major <= 4 && minor < 2 => 12,
+      (4, 2) => 40,
+      _ => 45
+    };
+    let mut buffer = vec![0u8; offset];
+
+    self.push_player(&mut buffer, &elim.eliminated);
+    self.push_player(&mut buffer, &elim.eliminator);
+    buffer.push(elim.gun_type);
+    buffer.extend_from_slice(&(elim.knocked as u32).to_le_bytes());
+
+    return buffer;
+  }
+
+  fn event(&self, id: &str, group: &str, metadata: &str, time: u32, data: Vec<u8>) -> Vec<u8> {
+    let mut buffer: Vec<u8> = vec![];
+    let payload = self.payload(data);
+
+    push_string(&mut buffer, id);
+    push_string(&mut buffer, group);
+    push_string(&mut buffer, metadata);
+    buffer.extend_from_slice(&time.to_le_bytes());
+    buffer.extend_from_slice(&time.to_le_bytes());
+    buffer.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    buffer.extend_from_slice(&payload);
+
+    return buffer;
+  }
+
+  fn data(&self, data: &[u8]) -> Vec<u8> {
+    let mut buffer: Vec<u8> = vec![];
+    let payload = self.payload(data.to_vec());
+
+    if self.file_version >= 4 {
+      buffer.extend_from_slice(&0u32.to_le_bytes());
+      buffer.extend_from_slice(&60000u32.to_le_bytes());
+      buffer.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    }
+    if self.file_version >= 6 {
+      buffer.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    }
+    buffer.extend_from_slice(&payload);
+
+    return buffer;
+  }
+
+  pub fn build(&self) -> Vec<u8> {
+    let mut buffer = self.meta();
+    push_chunk(&mut buffer, 0, &self.header());
+
+    if let Some(data) = &self.data_chunk {
+      push_chunk(&mut buffer, 1, &self.data(data));
+    }
+
+    if let Some(data) = &self.checkpoint {
+      let checkpoint = self.event("checkpoint_0", "checkpoint", "3", 0, data.clone());
+      push_chunk(&mut buffer, 2, &checkpoint);
+    }
+
+    for (i, elim) in self.eliminations.iter().enumerate() {
+      let event = self.event(&format!("Unsaved Replay_{}", i), "playerElim", "versionedEvent", elim.time, self.elimination(elim));
+      push_chunk(&mut buffer, 3, &event);
+    }
+
+    if let Some(stats) = &self.match_stats {
+      let mut data: Vec<u8> = vec![];
+      data.extend_from_slice(&0u32.to_le_bytes());
+      data.extend_from_slice(&0.5f32.to_le_bytes());
+      for value in stats {
+        data.extend_from_slice(&value.to_le_bytes());
+      }
+      push_chunk(&mut buffer, 3, &self.event("Unsaved Replay_stats", "AthenaReplayBrowserEvents", "AthenaMatchStats", self.length_in_ms, data));
+    }
+
+    if let Some((placement, total_players)) = self.team_stats {
+      let mut data: Vec<u8> = vec![];
+      data.extend_from_slice(&0u32.to_le_bytes());
+      data.extend_from_slice(&placement.to_le_bytes());
+      data.extend_from_slice(&total_players.to_le_bytes());
+      push_chunk(&mut buffer, 3, &self.event("Unsaved Replay_team", "AthenaReplayBrowserEvents", "AthenaMatchTeamStats", self.length_in_ms, data));
+    }
+
+    return buffer;
+  }
+}
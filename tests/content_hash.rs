@@ -0,0 +1,60 @@
+#![cfg(feature = "hash")]
+#![allow(clippy::needless_return)]
+
+mod common;
+
+use common::{FixtureElimination, FixturePlayer, ReplayFixture};
+use replay_reader::parser::{Parser, Replay};
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay");
+
+fn fixture(encrypted: bool) -> ReplayFixture {
+  let mut fixture = ReplayFixture::new(6, encrypted, "++Fortnite+Release-13.40");
+  fixture.eliminations = vec![
+    FixtureElimination { eliminated: FixturePlayer::Human(1), eliminator: FixturePlayer::Human(2), gun_type: 3, knocked: true, time: 60000 },
+    FixtureElimination { eliminated: FixturePlayer::Bot, eliminator: FixturePlayer::Human(2), gun_type: 5, knocked: false, time: 65000 }
+  ];
+  fixture.team_stats = Some((2, 100));
+  return fixture;
+}
+
+fn replay(fixture: &ReplayFixture) -> Replay {
+  return Parser::from_bytes(fixture.build()).finish().unwrap();
+}
+
+#[test]
+fn same_replay_hashes_the_same() {
+  let first = Parser::from_bytes(std::fs::read(FIXTURE).unwrap()).finish().unwrap();
+  let second = Parser::from_bytes(std::fs::read(FIXTURE).unwrap()).finish().unwrap();
+
+  assert_eq!(first.content_hash(), second.content_hash());
+  assert_eq!(first.content_hash_hex().len(), 64);
+}
+
+#[test]
+fn name_timestamp_and_encryption_are_left_out() {
+  let mut renamed = replay(&fixture(false));
+  let hash = renamed.content_hash();
+
+  renamed.meta.name = String::from("Renamed");
+  renamed.meta.timestamp = Some(0);
+  assert_eq!(renamed.content_hash(), hash);
+  assert_eq!(replay(&fixture(true)).content_hash(), hash);
+}
+
+#[test]
+fn eliminations_and_stats_change_the_hash() {
+  let original = replay(&fixture(false));
+
+  let mut knocked = original.clone();
+  knocked.eliminations[1].is_knocked = true;
+  assert_ne!(knocked.content_hash(), original.content_hash());
+
+  let mut placement = original.clone();
+  placement.team_match_stats.as_mut().unwrap().placement = 1;
+  assert_ne!(placement.content_hash(), original.content_hash());
+
+  let mut reordered = original.clone();
+  reordered.eliminations.reverse();
+  assert_ne!(reordered.content_hash(), original.content_hash());
+}
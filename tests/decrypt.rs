@@ -0,0 +1,153 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::parser::{Parser, REPLAY_MAGIC};
+use replay_reader::reader::{CipherMode, DecryptError, Reader};
+
+// AES-256 test vectors from NIST SP 800-38A, F.1.5 and F.2.6
+const KEY: &str = "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4";
+const PLAINTEXT: &str = "6bc1bee22e409f96e93d7e117393172a";
+const ECB_CIPHERTEXT: &str = "f3eed1bdb5d2a03c064b5a7e3db181f8";
+const CBC_CIPHERTEXT: &str = "f58c4c04d6e5f1ba779eabfb5f7bfbd6";
+
+fn hex(value: &str) -> Vec<u8> {
+  return (0..value.len()).step_by(2).map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap()).collect();
+}
+
+#[test]
+fn decrypts_the_ecb_vector() {
+  let mut data = hex(ECB_CIPHERTEXT);
+
+  let decrypted = CipherMode::Ecb.decrypt(&hex(KEY), &mut data).unwrap();
+
+  assert_eq!(decrypted, &hex(PLAINTEXT)[..]);
+}
+
+#[test]
+fn decrypts_the_cbc_vector() {
+  let mut iv = [0u8; 16];
+  for (i, byte) in iv.iter_mut().enumerate() {
+    *byte = i as u8;
+  }
+  let mut data = hex(CBC_CIPHERTEXT);
+
+  let decrypted = CipherMode::Cbc { iv }.decrypt(&hex(KEY), &mut data).unwrap();
+
+  assert_eq!(decrypted, &hex(PLAINTEXT)[..]);
+}
+
+#[test]
+fn decrypt_buffer_uses_ecb_by_default() {
+  let mut reader = Reader::from_bytes(vec![]);
+  reader.set_encryption_key(hex(KEY)).unwrap();
+  assert_eq!(reader.cipher_mode, CipherMode::Ecb);
+
+  // The vector followed by a block of PKCS#7 padding
+  let mut data = hex(ECB_CIPHERTEXT);
+  data.extend(hex("4c45dfb3b3b484ec35b0512dc8c1c4d6"));
+
+  let decrypted = reader.decrypt_buffer(data).unwrap();
+
+  assert_eq!(decrypted.buffer(), &hex(PLAINTEXT)[..]);
+}
+
+#[test]
+fn partial_blocks_are_an_error() {
+  let mut data = vec![0u8; 15];
+
+  assert!(CipherMode::Ecb.decrypt(&hex(KEY), &mut data).is_err());
+}
+
+fn decrypt(ciphertext: &str) -> Reader {
+  let mut reader = Reader::from_bytes(vec![]);
+  reader.set_encryption_key(hex(KEY)).unwrap();
+
+  return reader.decrypt_buffer(hex(ciphertext)).unwrap();
+}
+
+#[test]
+fn a_zero_last_field_survives_decryption() {
+  // Match stats with total traveled 0, a full block of padding follows the 48 bytes
+  let mut data = decrypt(concat!(
+    "90bfecad4211fa125effcc6e1fb233ea020ba0d21621c36514e740d4f0420d29",
+    "8326693780e07f63634bcd9ce10c167d4c45dfb3b3b484ec35b0512dc8c1c4d6"
+  ));
+  assert_eq!(data.len(), 48);
+
+  let stats = Parser::from_bytes(vec![]).parse_match_stats(&mut data).unwrap();
+
+  assert_eq!(stats.materials_used, 300);
+  assert_eq!(stats.total_traveled, 0);
+  assert!(data.is_eof());
+}
+
+#[test]
+fn partial_block_padding_is_cut_off_exactly() {
+  // Team match stats, 12 bytes and 4 bytes of padding
+  let mut data = decrypt("2d00047d364b2b7360b7baf467a73ec0");
+  assert_eq!(data.len(), 12);
+
+  let stats = Parser::from_bytes(vec![]).parse_team_match_stats(&mut data).unwrap();
+
+  assert_eq!(stats.placement, 1);
+  assert_eq!(stats.total_players, 100);
+}
+
+fn push_string(buffer: &mut Vec<u8>, value: &str) {
+  buffer.extend_from_slice(&(value.len() as i32 + 1).to_le_bytes());
+  buffer.extend_from_slice(value.as_bytes());
+  buffer.push(0);
+}
+
+// A file version 6 meta with is_encrypted == false and no key, the header chunk of 1.replay and a team stats
+// event that isn't encrypted
+fn unencrypted_replay() -> Vec<u8> {
+  let mut buffer: Vec<u8> = vec![];
+  buffer.extend_from_slice(&REPLAY_MAGIC.to_le_bytes());
+  buffer.extend_from_slice(&6u32.to_le_bytes());
+  buffer.extend_from_slice(&600000u32.to_le_bytes());
+  buffer.extend_from_slice(&2u32.to_le_bytes());
+  buffer.extend_from_slice(&14036559u32.to_le_bytes());
+  push_string(&mut buffer, "Unsaved Replay");
+  buffer.extend_from_slice(&0u32.to_le_bytes()); // is live
+  buffer.extend_from_slice(&637323461365600000u64.to_le_bytes());
+  buffer.extend_from_slice(&0u32.to_le_bytes()); // is compressed
+  buffer.extend_from_slice(&0u32.to_le_bytes()); // is encrypted
+  buffer.extend_from_slice(&0u32.to_le_bytes()); // key length
+
+  buffer.extend_from_slice(&std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay")).unwrap()[594..756]);
+
+  let mut event: Vec<u8> = vec![];
+  push_string(&mut event, "Unsaved Replay_team");
+  push_string(&mut event, "AthenaReplayBrowserEvents");
+  push_string(&mut event, "AthenaMatchTeamStats");
+  event.extend_from_slice(&600000u32.to_le_bytes());
+  event.extend_from_slice(&600000u32.to_le_bytes());
+  event.extend_from_slice(&12u32.to_le_bytes());
+  for value in [0u32, 3, 100] {
+    event.extend_from_slice(&value.to_le_bytes());
+  }
+  buffer.extend_from_slice(&3u32.to_le_bytes());
+  buffer.extend_from_slice(&(event.len() as i32).to_le_bytes());
+  buffer.extend_from_slice(&event);
+
+  return buffer;
+}
+
+#[test]
+fn unencrypted_events_are_read_without_a_key() {
+  let mut parser = Parser::from_bytes(unencrypted_replay());
+  parser.parse().unwrap();
+
+  assert!(!parser.meta.as_ref().unwrap().is_encrypted);
+  assert_eq!(parser.encryption_key(), None);
+  let team_stats = parser.team_match_stats.as_ref().unwrap();
+  assert_eq!(team_stats.placement, 3);
+  assert_eq!(team_stats.total_players, 100);
+}
+
+#[test]
+fn decrypting_without_a_key_is_an_error() {
+  let reader = Reader::from_bytes(vec![]);
+
+  assert!(matches!(reader.decrypt_buffer(vec![0; 16]), Err(DecryptError::MissingKey)));
+}
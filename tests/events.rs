@@ -0,0 +1,222 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::parser::{HeaderFlags, Parser};
+use replay_reader::reader::Reader;
+use replay_reader::weapons::DeathCause;
+
+#[test]
+fn reads_the_player_state_key_without_the_padding() {
+  let mut parser = Parser::from_bytes(vec![]);
+  let mut buffer: Vec<u8> = (0..32).collect();
+  buffer.extend_from_slice(&[16u8; 16]);
+
+  let key = parser.parse_player_state_key(&mut Reader::from_bytes(buffer)).unwrap();
+
+  assert_eq!(key.to_vec(), (0..32).collect::<Vec<u8>>());
+}
+
+#[test]
+fn stores_the_player_state_key_of_the_fixture() {
+  let mut parser = Parser::from_bytes(std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay")).unwrap());
+  parser.parse().unwrap();
+
+  let key = parser.player_state_keys.get("UnsavedReplay-2020.08.06-21.28.56_ReplayKey").unwrap();
+  assert_eq!(key[..4], [0xd4, 0x7e, 0x6f, 0x3e]);
+  assert!(parser.raw_events.iter().all(|event| event.info.metadata != "PlayerStateEncryptionKey"));
+}
+
+#[test]
+fn counts_the_weapons_of_the_fixture() {
+  let mut parser = Parser::from_bytes(std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay")).unwrap());
+  parser.parse().unwrap();
+
+  let breakdown = parser.weapon_breakdown();
+
+  assert_eq!(breakdown[&DeathCause::Shotgun], 25);
+  assert_eq!(breakdown[&DeathCause::Rifle], 23);
+  assert_eq!(breakdown[&DeathCause::Smg], 16);
+  assert_eq!(breakdown[&DeathCause::FallDamage], 1);
+  assert_eq!(breakdown.values().sum::<usize>(), parser.eliminations.iter().filter(|elim| !elim.is_knocked).count());
+  assert_eq!(parser.most_common_weapon(), Some(DeathCause::Shotgun));
+}
+
+#[test]
+fn no_eliminations_have_no_most_common_weapon() {
+  let parser = Parser::from_bytes(vec![]);
+
+  assert!(parser.weapon_breakdown().is_empty());
+  assert_eq!(parser.most_common_weapon(), None);
+}
+
+#[test]
+fn decodes_the_header_flags() {
+  let flags = HeaderFlags::from_bits(0x8000_0081);
+
+  assert!(flags.client_recorded && flags.async_recorded);
+  assert!(!flags.has_streaming_fixes && !flags.delta_checkpoints && !flags.net_relevancy_enabled);
+  assert_eq!(flags.unknown_bits(), 0x8000_0000);
+  assert_eq!(flags.raw, 0x8000_0081);
+}
+
+#[test]
+fn decodes_the_header_flags_of_the_fixture() {
+  let mut parser = Parser::from_bytes(std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay")).unwrap());
+  parser.parse().unwrap();
+
+  let flags = parser.header.unwrap().header_flags();
+
+  assert!(flags.client_recorded && flags.has_streaming_fixes && flags.game_specific_frame_data);
+  assert!(!flags.delta_checkpoints);
+  assert_eq!(flags.unknown_bits(), 0);
+}
+
+#[test]
+fn elimination_times_fit_into_the_recording() {
+  let mut parser = Parser::from_bytes(std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay")).unwrap());
+  parser.parse().unwrap();
+
+  let length = parser.meta.as_ref().unwrap().length();
+  for elim in &parser.eliminations {
+    // The match level is loaded as soon as the recording starts
+    assert_eq!(elim.time_into_match(), elim.time());
+    assert!(elim.time_into_match() <= length);
+  }
+}
+
+#[test]
+fn parsed_results_can_be_cloned_into_other_threads() {
+  let mut parser = Parser::from_bytes(std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay")).unwrap());
+  parser.parse().unwrap();
+
+  let eliminations = parser.eliminations.clone();
+  let header = parser.header.clone().unwrap();
+  let count = std::thread::spawn(move || {
+    assert_eq!(header.version.branch, "++Fortnite+Release-13.40");
+    return eliminations.len();
+  }).join().unwrap();
+
+  assert_eq!(count, parser.eliminations.len());
+}
+
+#[test]
+fn filtered_out_events_and_checkpoints_are_skipped() {
+  let mut parser = Parser::from_bytes(std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay")).unwrap())
+    .with_event_filter(|info| info.group == "playerElim");
+  parser.parse().unwrap();
+
+  assert_eq!(parser.eliminations.len(), 188);
+  assert!(parser.checkpoints.is_empty());
+  assert!(parser.match_stats.is_none());
+  assert!(parser.player_state_keys.is_empty());
+  assert!(parser.raw_events.is_empty());
+}
+
+#[test]
+fn checkpoints_reach_the_filter_with_the_group_from_the_file() {
+  let buffer = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay")).unwrap();
+  let mut parser = Parser::from_bytes(buffer.clone()).with_event_filter(|info| info.group == "checkpoint");
+  parser.parse().unwrap();
+
+  assert_eq!(parser.checkpoints.len(), 18);
+  assert!(parser.checkpoints.iter().all(|checkpoint| checkpoint.group == "checkpoint"));
+  assert!(parser.eliminations.is_empty());
+
+  let mut parser = Parser::from_bytes(buffer).with_event_filter(|info| info.group != "checkpoint");
+  parser.parse().unwrap();
+
+  assert!(parser.checkpoints.is_empty());
+  assert_eq!(parser.eliminations.len(), 188);
+}
+
+// [85 byte block][eliminated, eliminator: player type 17, id length, id][gun type: u8] followed by the knocked flag
+fn elimination_payload(knocked: &[u8]) -> Vec<u8> {
+  let mut buffer = vec![0u8; 85];
+  for id_byte in [0x11u8, 0x22] {
+    buffer.extend_from_slice(&[17, 16]);
+    buffer.extend_from_slice(&[id_byte; 16]);
+  }
+  buffer.push(3);
+  buffer.extend_from_slice(knocked);
+
+  return buffer;
+}
+
+fn parser_with_header() -> Parser {
+  let buffer = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay")).unwrap();
+  // The meta and the header chunk, which ends at 756
+  let mut parser = Parser::from_bytes(buffer[..756].to_vec());
+  parser.parse().unwrap();
+
+  return parser;
+}
+
+#[test]
+fn reads_the_knocked_flag_as_a_u32() {
+  let mut parser = parser_with_header();
+
+  parser.parse_elimination(&mut Reader::from_bytes(elimination_payload(&[1, 0, 0, 0])), 1000).unwrap();
+  parser.parse_elimination(&mut Reader::from_bytes(elimination_payload(&[0, 0, 0, 0])), 2000).unwrap();
+
+  assert!(parser.eliminations[0].is_knocked);
+  assert!(!parser.eliminations[1].is_knocked);
+  assert_eq!(parser.eliminations[0].eliminator.id.as_str(), "22222222222222222222222222222222");
+}
+
+#[test]
+fn a_single_byte_knocked_flag_is_an_error() {
+  let mut parser = parser_with_header();
+
+  assert!(parser.parse_elimination(&mut Reader::from_bytes(elimination_payload(&[1])), 1000).is_err());
+  assert!(parser.eliminations.is_empty());
+}
+
+#[test]
+fn keeps_bytes_after_the_knocked_flag() {
+  let mut parser = parser_with_header();
+  let mut data = Reader::from_bytes(elimination_payload(&[1, 0, 0, 0, 0xAA, 0xBB]));
+
+  parser.parse_elimination(&mut data, 1000).unwrap();
+
+  assert!(parser.eliminations[0].is_knocked);
+  assert_eq!(parser.eliminations[0].trailing_bytes, Some(vec![0xAA, 0xBB]));
+  assert!(data.is_eof());
+}
+
+#[test]
+fn the_fixture_has_nothing_after_the_knocked_flag() {
+  let mut parser = Parser::from_bytes(std::fs::read("1.replay").unwrap());
+  parser.parse().unwrap();
+
+  assert!(parser.eliminations.iter().all(|elim| elim.trailing_bytes.is_none()));
+}
+
+#[test]
+fn reads_the_positions_from_the_fixture() {
+  let mut parser = Parser::from_bytes(std::fs::read("1.replay").unwrap());
+  parser.parse().unwrap();
+
+  let first = &parser.eliminations[0];
+  assert_eq!(first.eliminated_position, Some((-69591.78, 81460.56, 5845.15)));
+  assert_eq!(first.eliminator_position, Some((0.0, 0.0, 0.0)));
+  assert_eq!(first.distance(), None);
+
+  let second = &parser.eliminations[1];
+  assert_eq!(second.eliminator_position, Some((-57688.18, 119128.766, 68380.71)));
+  assert!((second.distance().unwrap() - 70275.18).abs() < 1.0);
+}
+
+#[test]
+fn reads_the_positions_from_the_transforms() {
+  let mut parser = parser_with_header();
+  let mut payload = elimination_payload(&[0, 0, 0, 0]);
+  // The eliminated player's location after the event version, the unknown byte and the rotation, the eliminator's 40 bytes later
+  for (offset, value) in [(21, 300.0f32), (25, 400.0), (29, 0.0), (61, 0.0), (65, 0.0), (69, 120.0)] {
+    payload[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+  }
+
+  parser.parse_elimination(&mut Reader::from_bytes(payload), 1000).unwrap();
+
+  assert_eq!(parser.eliminations[0].eliminated_position, Some((300.0, 400.0, 0.0)));
+  assert_eq!(parser.eliminations[0].eliminator_position, Some((0.0, 0.0, 120.0)));
+  assert_eq!(parser.eliminations[0].distance(), Some(((300.0f32 * 300.0) + (400.0 * 400.0) + (120.0 * 120.0)).sqrt()));
+}
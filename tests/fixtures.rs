@@ -0,0 +1,392 @@
+#![allow(clippy::needless_return)]
+
+mod common;
+
+use common::{player_id_string, FixtureElimination, FixturePlayer, ReplayFixture};
+use replay_reader::parser::{ParseError, ParseMode, Parser, PlayerSummary};
+use replay_reader::weapons::DeathCause;
+
+fn elimination(eliminated: FixturePlayer, eliminator: FixturePlayer, gun_type: u8, knocked: bool, time: u32) -> FixtureElimination {
+  return FixtureElimination { eliminated, eliminator, gun_type, knocked, time };
+}
+
+fn parse(fixture: &ReplayFixture) -> Parser {
+  let mut parser = Parser::from_bytes(fixture.build());
+  parser.parse().unwrap();
+  return parser;
+}
+
+#[test]
+fn unencrypted_file_version_5_with_team_stats() {
+  let mut fixture = ReplayFixture::new(5, false, "++Fortnite+Release-12.41");
+  fixture.eliminations = vec![
+    elimination(FixturePlayer::Human(1), FixturePlayer::Human(2), 3, true, 60000),
+    elimination(FixturePlayer::Human(1), FixturePlayer::Human(2), 3, false, 65000),
+    elimination(FixturePlayer::Human(3), FixturePlayer::Human(2), 1, false, 90000)
+  ];
+  fixture.team_stats = Some((4, 100));
+  fixture.data_chunk = Some(vec![1, 2, 3, 4]);
+
+  let parser = parse(&fixture);
+  let meta = parser.meta.as_ref().unwrap();
+  let header = parser.header.as_ref().unwrap();
+
+  assert_eq!(meta.file_version, 5);
+  assert!(!meta.is_encrypted);
+  assert_eq!(meta.name, "Unsaved Replay");
+  assert_eq!(meta.length_in_ms, 600000);
+  assert_eq!((header.version.major, header.version.minor), (12, 41));
+  assert_eq!(header.engine_network_version, 16);
+  assert!(header.id.is_some());
+  assert_eq!(parser.eliminations.len(), 3);
+  assert_eq!(parser.eliminations.iter().filter(|elim| elim.is_knocked).count(), 1);
+  assert_eq!(parser.eliminations[0].eliminated.id.as_str(), player_id_string(1));
+  assert_eq!(parser.replay_data.len(), 1);
+  assert_eq!(parser.replay_data[0].buffer, vec![1, 2, 3, 4]);
+
+  let team_stats = parser.team_match_stats.as_ref().unwrap();
+  assert_eq!((team_stats.placement, team_stats.total_players), (4, 100));
+  assert!(parser.match_stats.is_none());
+}
+
+#[test]
+fn encrypted_file_version_6_with_match_stats() {
+  let mut fixture = ReplayFixture::new(6, true, "++Fortnite+Release-13.40");
+  fixture.eliminations = vec![
+    elimination(FixturePlayer::Human(5), FixturePlayer::Human(6), 3, false, 120000),
+    elimination(FixturePlayer::Human(7), FixturePlayer::Human(6), 2, false, 240000)
+  ];
+  fixture.match_stats = Some([1, 2, 300, 40, 0, 120, 900, 250, 180, 150000]);
+  fixture.team_stats = Some((1, 99));
+  fixture.data_chunk = Some(vec![9; 20]);
+
+  let parser = parse(&fixture);
+  let meta = parser.meta.as_ref().unwrap();
+
+  assert_eq!(meta.file_version, 6);
+  assert!(meta.is_encrypted);
+  assert_eq!(parser.encryption_key(), Some(&common::KEY[..]));
+  assert_eq!(parser.header.as_ref().unwrap().version.major, 13);
+  assert_eq!(parser.eliminations.len(), 2);
+  assert_eq!(parser.eliminations[1].death_cause, DeathCause::from_byte(2));
+  assert_eq!(parser.replay_data[0].buffer, vec![9; 20]);
+
+  let stats = parser.match_stats.as_ref().unwrap();
+  assert_eq!(stats.eliminations, 2);
+  assert_eq!(stats.total_traveled, 150000);
+  assert_eq!(parser.team_match_stats.as_ref().unwrap().placement, 1);
+}
+
+#[test]
+fn file_version_7_without_team_stats() {
+  let mut fixture = ReplayFixture::new(7, true, "++Fortnite+Release-14.60");
+  fixture.eliminations = vec![elimination(FixturePlayer::Human(8), FixturePlayer::Human(9), 4, false, 30000)];
+
+  let parser = parse(&fixture);
+
+  assert_eq!(parser.meta.as_ref().unwrap().file_version, 7);
+  assert!(parser.meta.as_ref().unwrap().custom_versions.is_empty());
+  assert_eq!(parser.eliminations.len(), 1);
+  assert!(parser.team_match_stats.is_none());
+  assert!(parser.match_stats.is_none());
+}
+
+#[test]
+fn old_replays_with_string_ids() {
+  for (branch, network_version) in [("++Fortnite+Release-4.1", 7), ("++Fortnite+Release-4.2", 9), ("++Fortnite+Release-6.10", 11)] {
+    let mut fixture = ReplayFixture::new(5, false, branch);
+    fixture.network_version = network_version;
+    fixture.engine_network_version = 9;
+    fixture.eliminations = vec![
+      elimination(FixturePlayer::Human(10), FixturePlayer::Human(11), 1, false, 1000),
+      elimination(FixturePlayer::Human(12), FixturePlayer::Human(11), 1, true, 2000)
+    ];
+
+    let parser = parse(&fixture);
+    let header = parser.header.as_ref().unwrap();
+
+    assert!(header.id.is_none(), "{}", branch);
+    assert_eq!(parser.eliminations.len(), 2, "{}", branch);
+    assert_eq!(parser.eliminations[0].eliminated.id.as_str(), player_id_string(10), "{}", branch);
+    assert_eq!(parser.eliminations[1].eliminator.id.as_str(), player_id_string(11), "{}", branch);
+    assert!(parser.eliminations[1].is_knocked, "{}", branch);
+  }
+}
+
+#[test]
+fn eliminations_display_unknown_death_causes_as_the_byte() {
+  let mut fixture = ReplayFixture::new(6, false, "++Fortnite+Release-13.40");
+  fixture.eliminations = vec![elimination(FixturePlayer::Human(1), FixturePlayer::Human(2), 200, false, 61000)];
+
+  let parser = parse(&fixture);
+  let elim = &parser.eliminations[0];
+
+  assert_eq!(elim.death_cause, DeathCause::Unknown(200));
+  assert!(elim.to_string().ends_with(" with 200 at 01:01"), "{}", elim);
+}
+
+#[test]
+fn reads_the_header_guid_from_network_version_12_on() {
+  for (network_version, has_guid) in [(11, false), (12, true), (13, true)] {
+    let mut fixture = ReplayFixture::new(6, false, "++Fortnite+Release-7.40");
+    fixture.network_version = network_version;
+
+    let parser = parse(&fixture);
+    let header = parser.header.as_ref().unwrap();
+
+    assert_eq!(header.network_version, network_version);
+    assert_eq!(header.id.is_some(), has_guid, "network version {}", network_version);
+    // A misplaced guid would shift everything after it
+    assert_eq!(header.version.changelist, 14036559, "network version {}", network_version);
+    assert_eq!(header.version.branch, "++Fortnite+Release-7.40", "network version {}", network_version);
+    assert_eq!(header.flags, 3, "network version {}", network_version);
+  }
+}
+
+#[test]
+fn bots_are_kept_apart_from_players() {
+  let mut fixture = ReplayFixture::new(6, true, "++Fortnite+Release-11.31");
+  fixture.eliminations = vec![
+    elimination(FixturePlayer::Bot, FixturePlayer::Human(13), 3, false, 1000),
+    elimination(FixturePlayer::NamedBot("Bot, \"Tilted\""), FixturePlayer::Human(13), 3, false, 2000),
+    elimination(FixturePlayer::Human(13), FixturePlayer::NamedBot("Bot, \"Tilted\""), 3, false, 3000)
+  ];
+
+  let parser = parse(&fixture);
+  let roster = parser.roster();
+
+  assert_eq!(parser.eliminations.len(), 3);
+  assert!(parser.eliminations[0].eliminated.is_bot);
+  assert_eq!(parser.eliminations[1].eliminated.name, "Bot, \"Tilted\"");
+  assert_eq!(roster.len(), 3);
+  assert_eq!(roster.iter().filter(|player| player.is_bot).count(), 2);
+}
+
+#[test]
+fn splits_bot_eliminations_from_player_ones() {
+  let mut fixture = ReplayFixture::new(6, false, "++Fortnite+Release-11.31");
+  fixture.eliminations = vec![
+    elimination(FixturePlayer::Bot, FixturePlayer::Human(14), 3, false, 1000),
+    elimination(FixturePlayer::Human(15), FixturePlayer::Human(14), 3, false, 2000),
+    elimination(FixturePlayer::Human(14), FixturePlayer::NamedBot("Bot"), 3, false, 3000),
+    elimination(FixturePlayer::Human(16), FixturePlayer::Human(17), 3, true, 4000)
+  ];
+
+  let parser = parse(&fixture);
+  let player_times: Vec<u32> = parser.player_eliminations().map(|elim| elim.timestamp).collect();
+  let bot_times: Vec<u32> = parser.bot_eliminations().map(|elim| elim.timestamp).collect();
+
+  assert_eq!(player_times, vec![2000, 4000]);
+  assert_eq!(bot_times, vec![1000, 3000]);
+}
+
+#[test]
+fn summarizes_a_player() {
+  let mut fixture = ReplayFixture::new(6, true, "++Fortnite+Release-13.40");
+  fixture.eliminations = vec![
+    elimination(FixturePlayer::Human(21), FixturePlayer::Human(20), 3, true, 1000),
+    elimination(FixturePlayer::Human(21), FixturePlayer::Human(20), 3, false, 2000),
+    elimination(FixturePlayer::Human(22), FixturePlayer::Human(20), 4, false, 3000),
+    elimination(FixturePlayer::Human(23), FixturePlayer::Human(20), 4, true, 4000),
+    elimination(FixturePlayer::Human(20), FixturePlayer::Human(23), 5, false, 5000),
+    elimination(FixturePlayer::Human(23), FixturePlayer::Human(23), 1, false, 6000)
+  ];
+
+  let parser = parse(&fixture);
+  let id = parser.eliminations[0].eliminator.id.clone();
+  let summary = parser.player_summary(&id);
+
+  assert_eq!(summary, PlayerSummary { kills: 2, deaths: 1, knocks: 2, assists: None });
+  assert_eq!(summary.kda(), 2.0);
+
+  let other = parser.player_summary(&parser.eliminations[5].eliminated.id);
+  assert_eq!(other, PlayerSummary { kills: 1, deaths: 1, knocks: 0, assists: None });
+}
+
+#[test]
+fn the_owner_is_only_inferred_when_unambiguous() {
+  let mut fixture = ReplayFixture::new(6, true, "++Fortnite+Release-13.40");
+  fixture.eliminations = vec![
+    elimination(FixturePlayer::Human(31), FixturePlayer::Human(30), 3, false, 1000),
+    elimination(FixturePlayer::Human(32), FixturePlayer::Human(30), 3, false, 2000),
+    elimination(FixturePlayer::Human(33), FixturePlayer::Human(34), 3, false, 3000)
+  ];
+
+  let parser = parse(&fixture);
+  assert_eq!(parser.owner_id(), None);
+
+  fixture.match_stats = Some([4, 2, 0, 0, 0, 0, 0, 0, 0, 0]);
+  let parser = parse(&fixture);
+  let owner = parser.owner_id().unwrap();
+  assert_eq!(owner.as_str(), player_id_string(30));
+  assert_eq!(parser.player_summary(&owner).assists, Some(4));
+  assert_eq!(parser.player_summary(&parser.eliminations[2].eliminator.id).assists, None);
+
+  // Players 30 and 34 both have one kill
+  fixture.eliminations.remove(1);
+  fixture.match_stats = Some([4, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+  assert_eq!(parse(&fixture).owner_id(), None);
+}
+
+#[test]
+fn live_replays_stop_at_an_incomplete_last_chunk() {
+  let mut fixture = ReplayFixture::new(6, true, "++Fortnite+Release-13.40");
+  fixture.is_live = true;
+  fixture.eliminations = vec![
+    elimination(FixturePlayer::Human(40), FixturePlayer::Human(41), 3, false, 1000),
+    elimination(FixturePlayer::Human(42), FixturePlayer::Human(41), 3, false, 2000)
+  ];
+  let complete = fixture.build();
+  fixture.eliminations.pop();
+  let last_chunk_offset = fixture.build().len();
+
+  // Cut into the last chunk's payload and into its type and size
+  for cut in [10, complete.len() - last_chunk_offset - 4] {
+    let mut parser = Parser::from_bytes(complete[..complete.len() - cut].to_vec());
+    parser.parse().unwrap();
+
+    assert!(parser.meta.as_ref().unwrap().is_live);
+    assert!(parser.is_partial, "cut {}", cut);
+    assert_eq!(parser.eliminations.len(), 1, "cut {}", cut);
+  }
+
+  let mut parser = Parser::from_bytes(complete);
+  parser.parse().unwrap();
+  assert!(!parser.is_partial);
+  assert_eq!(parser.eliminations.len(), 2);
+}
+
+#[test]
+fn finished_replays_still_fail_on_an_incomplete_last_chunk() {
+  let mut fixture = ReplayFixture::new(6, true, "++Fortnite+Release-13.40");
+  fixture.eliminations = vec![elimination(FixturePlayer::Human(40), FixturePlayer::Human(41), 3, false, 1000)];
+  let mut buffer = fixture.build();
+  buffer.truncate(buffer.len() - 10);
+
+  let mut parser = Parser::from_bytes(buffer);
+
+  assert!(matches!(parser.parse(), Err(ParseError::InvalidChunkSize { .. })));
+  assert!(!parser.is_partial);
+}
+
+fn fixture_with_a_broken_elimination() -> ReplayFixture {
+  let mut fixture = ReplayFixture::new(6, true, "++Fortnite+Release-13.40");
+  fixture.eliminations = vec![
+    elimination(FixturePlayer::Human(50), FixturePlayer::Human(51), 3, false, 1000),
+    // A player id that is longer than the payload
+    elimination(FixturePlayer::Raw(&[17, 200, 1, 2, 3]), FixturePlayer::Human(51), 3, false, 2000),
+    elimination(FixturePlayer::Human(52), FixturePlayer::Human(51), 3, false, 3000)
+  ];
+  fixture.team_stats = Some((1, 100));
+
+  return fixture;
+}
+
+#[test]
+fn compressed_replay_data_without_a_decompressor_is_skipped() {
+  let mut fixture = ReplayFixture::new(6, false, "++Fortnite+Release-13.40");
+  fixture.compressed = true;
+  fixture.data_chunk = Some(vec![1, 2, 3, 4]);
+  fixture.eliminations = vec![elimination(FixturePlayer::Human(1), FixturePlayer::Human(2), 3, false, 60000)];
+
+  let parser = parse(&fixture);
+
+  assert!(parser.meta.as_ref().unwrap().is_compressed);
+  assert!(parser.replay_data.is_empty());
+  assert_eq!(parser.skipped_chunks.len(), 1);
+  assert_eq!(parser.skipped_chunks[0].location.index, 1);
+  assert!(matches!(parser.skipped_chunks[0].error, ParseError::Decompress(_)));
+  assert_eq!(parser.eliminations.len(), 1);
+}
+
+#[test]
+fn strict_parses_stop_at_the_first_broken_chunk() {
+  let mut parser = Parser::from_bytes(fixture_with_a_broken_elimination().build());
+
+  assert!(parser.parse().is_err());
+  assert_eq!(parser.eliminations.len(), 1);
+  assert!(parser.skipped_chunks.is_empty());
+}
+
+#[test]
+fn lenient_parses_skip_broken_chunks() {
+  let buffer = fixture_with_a_broken_elimination().build();
+  let mut parser = Parser::builder().parse_mode(ParseMode::Lenient).build_from_bytes(buffer).unwrap();
+
+  parser.parse().unwrap();
+
+  assert_eq!(parser.eliminations.len(), 2);
+  assert_eq!(parser.eliminations[1].timestamp, 3000);
+  assert_eq!(parser.team_match_stats.as_ref().unwrap().placement, 1);
+  assert_eq!(parser.skipped_chunks.len(), 1);
+  assert_eq!(parser.skipped_chunks[0].location.index, 2);
+  assert!(matches!(parser.skipped_chunks[0].error, ParseError::Reader(_)));
+  assert!(!parser.is_partial);
+}
+
+#[test]
+fn lenient_parses_stop_at_a_chunk_size_past_the_end() {
+  let mut buffer = fixture_with_a_broken_elimination().build();
+  buffer.extend_from_slice(&3u32.to_le_bytes());
+  buffer.extend_from_slice(&1000i32.to_le_bytes());
+  let mut parser = Parser::builder().parse_mode(ParseMode::Lenient).build_from_bytes(buffer).unwrap();
+
+  parser.parse().unwrap();
+
+  assert_eq!(parser.eliminations.len(), 2);
+  assert_eq!(parser.skipped_chunks.len(), 2);
+  assert!(matches!(parser.skipped_chunks[1].error, ParseError::InvalidChunkSize { .. }));
+  assert!(parser.is_partial);
+}
+
+#[test]
+fn diffs_the_stats_of_two_replays() {
+  let mut earlier = ReplayFixture::new(6, true, "++Fortnite+Release-13.40");
+  earlier.eliminations = vec![
+    elimination(FixturePlayer::Human(60), FixturePlayer::Human(61), 3, false, 1000),
+    elimination(FixturePlayer::Human(62), FixturePlayer::Human(61), 3, false, 2000)
+  ];
+  earlier.match_stats = Some([1, 2, 300, 40, 0, 120, 900, 250, 180, 150000]);
+  earlier.team_stats = Some((12, 100));
+
+  let mut later = ReplayFixture::new(6, true, "++Fortnite+Release-13.40");
+  later.eliminations = vec![elimination(FixturePlayer::Human(62), FixturePlayer::Bot, 3, false, 1000)];
+  later.match_stats = Some([3, 0, 150, 40, 1, 300, 400, 500, 200, 90000]);
+  later.team_stats = Some((3, 98));
+
+  let earlier = Parser::from_bytes(earlier.build()).finish().unwrap();
+  let later = Parser::from_bytes(later.build()).finish().unwrap();
+  let diff = earlier.diff(&later);
+
+  let stats = diff.match_stats.as_ref().unwrap();
+  assert_eq!(stats.assists, 2);
+  assert_eq!(stats.eliminations, -2);
+  assert_eq!(stats.weapon_damage, -150);
+  assert_eq!(stats.other_damage, 0);
+  assert_eq!(stats.revives, 1);
+  assert_eq!(stats.damage_taken, 180);
+  assert_eq!(stats.damage_to_structures, -500);
+  assert_eq!(stats.materials_gathered, 250);
+  assert_eq!(stats.materials_used, 20);
+  assert_eq!(stats.total_traveled, -60000);
+  assert_eq!(stats.accuracy, 0.0);
+  assert_eq!(diff.placement, Some(-9));
+  assert_eq!(diff.total_players, Some(-2));
+  assert_eq!(diff.eliminations, -1);
+  assert_eq!(diff.shared_players.len(), 1);
+  assert_eq!(diff.shared_players[0].as_str(), player_id_string(62));
+
+  // Diffing the other way around flips every delta
+  assert_eq!(later.diff(&earlier).match_stats.unwrap().weapon_damage, 150);
+}
+
+#[test]
+fn diffs_without_stats_only_compare_the_eliminations() {
+  let replay = Parser::from_bytes(ReplayFixture::new(6, false, "++Fortnite+Release-13.40").build()).finish().unwrap();
+  let diff = replay.diff(&replay);
+
+  assert_eq!(diff.match_stats, None);
+  assert_eq!(diff.placement, None);
+  assert_eq!(diff.eliminations, 0);
+  assert!(diff.shared_players.is_empty());
+}
@@ -0,0 +1,60 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::parser::Parser;
+use std::fs;
+
+fn fixture() -> Vec<u8> {
+  return fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay")).unwrap();
+}
+
+#[test]
+fn parsing_appended_pieces_matches_a_full_parse() {
+  let buffer = fixture();
+  let mut full = Parser::from_bytes(buffer.clone());
+  full.parse().unwrap();
+
+  let mut live = Parser::from_bytes(vec![]);
+  let mut parsed_chunks = 0;
+  for piece in buffer.chunks(4096) {
+    live.append(piece).unwrap();
+    parsed_chunks += live.parse_available().unwrap();
+  }
+
+  assert_eq!(parsed_chunks, full.chunks.len());
+  assert_eq!(live.eliminations.len(), full.eliminations.len());
+  assert_eq!(live.reader.offset, buffer.len());
+  for (live_elim, full_elim) in live.eliminations.iter().zip(&full.eliminations) {
+    assert_eq!(live_elim.timestamp, full_elim.timestamp);
+    assert_eq!(live_elim.eliminated, full_elim.eliminated);
+  }
+}
+
+#[test]
+fn incomplete_chunks_wait_for_more_bytes() {
+  let buffer = fixture();
+  // The meta ends at 594 and the header chunk at 756
+  let mut live = Parser::from_bytes(buffer[..700].to_vec());
+
+  assert_eq!(live.parse_available().unwrap(), 0);
+  assert!(live.meta.is_some());
+  assert!(live.header.is_none());
+  assert_eq!(live.reader.offset, 594);
+
+  live.append(&buffer[700..800]).unwrap();
+  assert_eq!(live.parse_available().unwrap(), 1);
+  assert!(live.header.is_some());
+  assert_eq!(live.reader.offset, 756);
+}
+
+#[test]
+fn incomplete_meta_waits_for_more_bytes() {
+  let buffer = fixture();
+  let mut live = Parser::from_bytes(buffer[..100].to_vec());
+
+  assert_eq!(live.parse_available().unwrap(), 0);
+  assert!(live.meta.is_none());
+
+  live.append(&buffer[100..600]).unwrap();
+  assert_eq!(live.parse_available().unwrap(), 0);
+  assert!(live.meta.is_some());
+}
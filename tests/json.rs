@@ -0,0 +1,48 @@
+#![cfg(feature = "json")]
+#![allow(clippy::needless_return)]
+
+use replay_reader::parser::Parser;
+use replay_reader::weapons::DeathCause;
+
+#[test]
+fn builds_one_document_for_the_replay() {
+  let mut parser = Parser::from_bytes(std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay")).unwrap());
+  parser.parse().unwrap();
+
+  let document = parser.to_json_value();
+
+  assert_eq!(document["meta"]["name"], "Unsaved Replay");
+  assert_eq!(document["header"]["version"]["major"], 13);
+  assert_eq!(document["match_stats"]["eliminations"], 6);
+  assert_eq!(document["team_match_stats"]["placement"], 1);
+  assert_eq!(document["players"].as_array().unwrap().len(), parser.roster().len());
+  assert_eq!(document["eliminations"].as_array().unwrap().len(), 188);
+  assert_eq!(document["eliminations"][0]["timestamp"], 81787);
+
+  let reparsed: serde_json::Value = serde_json::from_str(&parser.to_json_string_pretty().unwrap()).unwrap();
+  assert_eq!(reparsed, document);
+}
+
+#[test]
+fn missing_parts_are_null() {
+  let parser = Parser::from_bytes(vec![]);
+
+  let document = parser.to_json_value();
+
+  assert!(document["meta"].is_null());
+  assert!(document["match_stats"].is_null());
+  assert_eq!(document["eliminations"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn death_causes_are_names_or_bytes() {
+  let death_causes = vec![DeathCause::Rifle, DeathCause::Unknown(50)];
+
+  let json = serde_json::to_string(&death_causes).unwrap();
+
+  assert_eq!(json, "[\"Rifle\",50]");
+  assert_eq!(serde_json::from_str::<Vec<DeathCause>>(&json).unwrap(), death_causes);
+  assert_eq!(serde_json::from_str::<DeathCause>("4").unwrap(), DeathCause::Rifle);
+  assert!(serde_json::from_str::<DeathCause>("\"Spoon\"").is_err());
+  assert!(serde_json::from_str::<DeathCause>("300").is_err());
+}
@@ -0,0 +1,57 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::parser::Parser;
+use replay_reader::reader::Reader;
+
+// [unknown: u32][accuracy: f32][assists, eliminations, weapon damage, other damage, revives, damage taken,
+// damage to structures, materials gathered, materials used, total traveled: u32], padded to the AES block size
+fn match_stats_buffer() -> Vec<u8> {
+  let mut buffer: Vec<u8> = vec![];
+
+  buffer.extend_from_slice(&0u32.to_le_bytes());
+  buffer.extend_from_slice(&0.25f32.to_le_bytes());
+  for value in 1..=10u32 {
+    buffer.extend_from_slice(&(value * 111).to_le_bytes());
+  }
+  buffer.extend_from_slice(&[16u8; 16]);
+
+  return buffer;
+}
+
+#[test]
+fn parses_every_match_stats_field() {
+  let mut parser = Parser::from_bytes(vec![]);
+  let mut data = Reader::from_bytes(match_stats_buffer());
+
+  let stats = parser.parse_match_stats(&mut data).unwrap();
+
+  assert_eq!(stats.accuracy, 0.25);
+  assert_eq!(stats.assists, 111);
+  assert_eq!(stats.eliminations, 222);
+  assert_eq!(stats.weapon_damage, 333);
+  assert_eq!(stats.other_damage, 444);
+  assert_eq!(stats.revives, 555);
+  assert_eq!(stats.damage_taken, 666);
+  assert_eq!(stats.damage_to_structures, 777);
+  assert_eq!(stats.materials_gathered, 888);
+  assert_eq!(stats.materials_used, 999);
+  assert_eq!(stats.total_traveled, 1110);
+}
+
+#[test]
+fn match_stats_stop_before_the_padding() {
+  let mut parser = Parser::from_bytes(vec![]);
+  let mut data = Reader::from_bytes(match_stats_buffer());
+
+  parser.parse_match_stats(&mut data).unwrap();
+
+  assert_eq!(data.offset, 48);
+}
+
+#[test]
+fn truncated_match_stats_are_an_error() {
+  let mut parser = Parser::from_bytes(vec![]);
+  let mut data = Reader::from_bytes(match_stats_buffer()[..40].to_vec());
+
+  assert!(parser.parse_match_stats(&mut data).is_err());
+}
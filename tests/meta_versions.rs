@@ -0,0 +1,214 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::parser::{KeySource, ParseError, Parser, HEADER_MAGIC, MIN_META_SIZE, REPLAY_MAGIC};
+use replay_reader::version::LATEST_FILE_VERSION;
+
+fn push_string(buffer: &mut Vec<u8>, value: &str) {
+  buffer.extend_from_slice(&(value.len() as i32 + 1).to_le_bytes());
+  buffer.extend_from_slice(value.as_bytes());
+  buffer.push(0);
+}
+
+// Writes the meta section the way the engine does for the given file version
+fn meta_bytes(file_version: u32) -> Vec<u8> {
+  let mut buffer: Vec<u8> = vec![];
+
+  buffer.extend_from_slice(&REPLAY_MAGIC.to_le_bytes());
+  buffer.extend_from_slice(&file_version.to_le_bytes());
+  if file_version >= 7 {
+    buffer.extend_from_slice(&1u32.to_le_bytes());
+    buffer.extend_from_slice(&[0xAB; 16]);
+    buffer.extend_from_slice(&3i32.to_le_bytes());
+  }
+  buffer.extend_from_slice(&600000u32.to_le_bytes());
+  buffer.extend_from_slice(&2u32.to_le_bytes());
+  buffer.extend_from_slice(&14036559u32.to_le_bytes());
+  push_string(&mut buffer, "Unsaved Replay   ");
+  buffer.extend_from_slice(&0u32.to_le_bytes());
+  if file_version >= 3 {
+    buffer.extend_from_slice(&637323461365600000u64.to_le_bytes());
+  }
+  if file_version >= 2 {
+    buffer.extend_from_slice(&1u32.to_le_bytes());
+  }
+  if file_version >= 6 {
+    buffer.extend_from_slice(&1u32.to_le_bytes());
+    buffer.extend_from_slice(&32u32.to_le_bytes());
+    buffer.extend_from_slice(&[7; 32]);
+  }
+
+  return buffer;
+}
+
+#[test]
+fn parses_the_meta_of_every_known_file_version() {
+  for file_version in 0..=LATEST_FILE_VERSION {
+    let buffer = meta_bytes(file_version);
+    let buffer_len = buffer.len();
+    let mut parser = Parser::from_bytes(buffer);
+
+    let meta = parser.parse_meta_only().unwrap();
+
+    assert_eq!(meta.file_version, file_version);
+    assert_eq!(meta.length_in_ms, 600000, "file version {}", file_version);
+    assert_eq!(meta.network_version, 2, "file version {}", file_version);
+    assert_eq!(meta.changelist, 14036559, "file version {}", file_version);
+    assert_eq!(meta.name, "Unsaved Replay", "file version {}", file_version);
+    assert_eq!(meta.timestamp.is_some(), file_version >= 3, "file version {}", file_version);
+    assert_eq!(meta.is_compressed, file_version >= 2, "file version {}", file_version);
+    assert_eq!(meta.is_encrypted, file_version >= 6, "file version {}", file_version);
+    assert_eq!(meta.custom_versions.len(), if file_version >= 7 { 1 } else { 0 }, "file version {}", file_version);
+    assert_eq!(parser.reader.offset, buffer_len, "file version {}", file_version);
+  }
+}
+
+#[test]
+fn reads_custom_versions() {
+  let mut parser = Parser::from_bytes(meta_bytes(7));
+
+  let meta = parser.parse_meta_only().unwrap();
+
+  assert_eq!(meta.custom_versions, vec![(String::from("abababababababababababababababab"), 3)]);
+}
+
+#[test]
+fn rejects_newer_file_versions() {
+  let mut parser = Parser::from_bytes(meta_bytes(LATEST_FILE_VERSION + 1));
+
+  match parser.parse_meta_only() {
+    Err(ParseError::UnsupportedFileVersion(version)) => assert_eq!(version, LATEST_FILE_VERSION + 1),
+    _ => panic!("expected an unsupported file version error")
+  }
+}
+
+#[test]
+fn builder_meta_only_stops_after_the_meta() {
+  let mut parser = Parser::builder().meta_only(true).build_from_bytes(meta_bytes(LATEST_FILE_VERSION)).unwrap();
+
+  parser.parse().unwrap();
+
+  assert!(parser.meta.is_some());
+  assert!(parser.header.is_none());
+}
+
+#[test]
+fn builder_rejects_short_keys() {
+  assert!(Parser::builder().encryption_key(vec![0; 16]).build_from_bytes(meta_bytes(6)).is_err());
+}
+
+#[test]
+fn exposes_the_key_from_the_file() {
+  let mut parser = Parser::from_bytes(meta_bytes(6));
+  parser.parse_meta_only().unwrap();
+
+  assert_eq!(parser.encryption_key(), Some(&[7u8; 32][..]));
+  assert_eq!(parser.encryption_key_source, Some(KeySource::File));
+}
+
+#[test]
+fn exposes_the_key_from_the_caller() {
+  let mut parser = Parser::builder().encryption_key(vec![9; 32]).build_from_bytes(meta_bytes(6)).unwrap();
+  parser.parse_meta_only().unwrap();
+
+  assert_eq!(parser.encryption_key(), Some(&[9u8; 32][..]));
+  assert_eq!(parser.encryption_key_source, Some(KeySource::Caller));
+}
+
+#[test]
+fn unencrypted_replays_have_no_key() {
+  let mut parser = Parser::from_bytes(meta_bytes(5)).with_encryption_key(vec![9; 32]).unwrap();
+  parser.parse_meta_only().unwrap();
+
+  assert_eq!(parser.encryption_key(), None);
+  assert_eq!(parser.encryption_key_source, None);
+}
+
+#[test]
+fn rejects_files_that_are_not_replays() {
+  // The start of a zip file
+  let mut parser = Parser::from_bytes(b"PK\x03\x04\x14\x00\x00\x00\x08\x00".to_vec());
+
+  match parser.parse() {
+    Err(ParseError::BadMagic { found, expected }) => {
+      assert_eq!(found, 0x04034b50);
+      assert_eq!(expected, REPLAY_MAGIC);
+    },
+    _ => panic!("expected a bad magic error")
+  }
+}
+
+#[test]
+fn rejects_headers_with_the_wrong_magic() {
+  let mut buffer = meta_bytes(5);
+  buffer.extend_from_slice(&0u32.to_le_bytes());
+  buffer.extend_from_slice(&8i32.to_le_bytes());
+  buffer.extend_from_slice(&REPLAY_MAGIC.to_le_bytes());
+  buffer.extend_from_slice(&[0; 4]);
+  let mut parser = Parser::from_bytes(buffer);
+
+  match parser.parse() {
+    Err(ParseError::BadMagic { found, expected }) => {
+      assert_eq!(found, REPLAY_MAGIC);
+      assert_eq!(expected, HEADER_MAGIC);
+    },
+    _ => panic!("expected a bad magic error")
+  }
+}
+
+#[test]
+fn rejects_empty_and_cut_off_files() {
+  let mut cut_off = meta_bytes(5);
+  cut_off.truncate(20);
+
+  for buffer in [vec![], vec![0x7F, 0xE2], cut_off] {
+    let len = buffer.len();
+    let mut parser = Parser::from_bytes(buffer);
+
+    match parser.parse() {
+      Err(ParseError::TooSmall { len: found_len, minimum }) => {
+        assert_eq!(found_len, len);
+        assert_eq!(minimum, MIN_META_SIZE);
+      },
+      _ => panic!("expected a too small error for {} bytes", len)
+    }
+  }
+}
+
+#[test]
+fn the_smallest_meta_is_min_meta_size_bytes() {
+  let mut parser = Parser::from_bytes(meta_bytes(0));
+  parser.parse_meta_only().unwrap();
+
+  // meta_bytes writes a 17 character name, the bare minimum has an empty one
+  assert_eq!(parser.reader.offset - 18, MIN_META_SIZE);
+}
+
+#[test]
+fn converts_the_recording_time() {
+  let mut parser = Parser::from_bytes(meta_bytes(5));
+  let meta = parser.parse_meta_only().unwrap();
+
+  // 2020-08-06 21:28:56.56 UTC
+  assert_eq!(meta.recorded_since_unix_epoch(), Some(std::time::Duration::from_millis(1596749336560)));
+  assert!(meta.recorded_at().is_some());
+}
+
+#[test]
+fn implausible_recording_times_are_none() {
+  let mut parser = Parser::from_bytes(meta_bytes(5));
+  let mut meta = parser.parse_meta_only().unwrap().clone();
+
+  // Before the .NET ticks of the Unix epoch, which used to wrap around
+  meta.timestamp = Some(1000);
+  assert_eq!(meta.recorded_since_unix_epoch(), None);
+  assert_eq!(meta.recorded_at(), None);
+
+  // 2200-01-01 and 2010-01-01
+  for ticks in [693937152000000000u64, 633979008000000000] {
+    meta.timestamp = Some(ticks);
+    assert_eq!(meta.recorded_at(), None, "{} ticks", ticks);
+  }
+
+  meta.timestamp = Some(u64::MAX);
+  assert_eq!(meta.recorded_at(), None);
+}
@@ -0,0 +1,23 @@
+#![cfg(feature = "mmap")]
+#![allow(clippy::needless_return)]
+
+use replay_reader::parser::Parser;
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay");
+
+#[test]
+fn mapped_replays_parse_like_read_ones() {
+  let mut mapped = Parser::from_mmap(FIXTURE).unwrap();
+  let mut read = Parser::new(FIXTURE).unwrap();
+  mapped.parse().unwrap();
+  read.parse().unwrap();
+
+  assert_eq!(mapped.reader.len(), read.reader.len());
+  assert_eq!(mapped.eliminations.len(), read.eliminations.len());
+  assert_eq!(mapped.header.unwrap().version.branch, read.header.unwrap().version.branch);
+}
+
+#[test]
+fn missing_files_are_an_error() {
+  assert!(Parser::from_mmap("/this/file/does/not/exist.replay").is_err());
+}
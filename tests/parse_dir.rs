@@ -0,0 +1,46 @@
+#![cfg(feature = "std")]
+#![allow(clippy::needless_return)]
+
+use replay_reader::batch::parse_dir;
+use std::fs;
+use std::path::PathBuf;
+
+fn fixture_dir(name: &str) -> PathBuf {
+  let dir = std::env::temp_dir().join(format!("replay-reader-{}-{}", name, std::process::id()));
+  let _ = fs::remove_dir_all(&dir);
+  fs::create_dir_all(&dir).unwrap();
+
+  let fixture = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("1.replay");
+  for i in 0..4 {
+    fs::copy(&fixture, dir.join(format!("{}.replay", i))).unwrap();
+  }
+
+  fs::write(dir.join("broken.replay"), [1, 2, 3]).unwrap();
+  fs::write(dir.join("notes.txt"), "not a replay").unwrap();
+
+  return dir;
+}
+
+#[test]
+fn parses_each_replay_in_the_directory() {
+  let dir = fixture_dir("parse-dir");
+
+  let results = parse_dir(&dir).unwrap();
+  fs::remove_dir_all(&dir).unwrap();
+
+  assert_eq!(results.len(), 5);
+
+  for (path, result) in &results[..4] {
+    let summary = result.as_ref().unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+    assert_eq!(summary.eliminations.len(), 188);
+    assert_eq!(summary.team_match_stats.as_ref().unwrap().placement, 1);
+  }
+
+  assert_eq!(results[4].0.file_name().unwrap(), "broken.replay");
+  assert!(results[4].1.is_err());
+}
+
+#[test]
+fn missing_directory_is_an_error() {
+  assert!(parse_dir("/this/directory/does/not/exist").is_err());
+}
@@ -0,0 +1,50 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::parser::{Player, PlayerId};
+use std::collections::HashSet;
+
+fn player(id: &str, name: &str, is_bot: bool) -> Player {
+  return Player { id: PlayerId::new(id), name: String::from(name), is_bot };
+}
+
+#[test]
+fn players_are_equal_by_id() {
+  assert_eq!(player("4494b3820c5847708b03b6b80e92bdd2", "", false), player("4494b3820c5847708b03b6b80e92bdd2", "Nils", false));
+  assert_ne!(player("4494b3820c5847708b03b6b80e92bdd2", "Nils", false), player("642492fc42586200a99e66a00aae9282", "Nils", false));
+}
+
+#[test]
+fn bots_are_equal_by_name() {
+  assert_eq!(player("", "Kyle", true), player("", "Kyle", true));
+  assert_ne!(player("", "Kyle", true), player("", "Jonesy", true));
+  assert_ne!(player("", "Kyle", true), player("", "Kyle", false));
+}
+
+#[test]
+fn dedupes_players_in_a_set() {
+  let players: HashSet<Player> = vec![
+    player("4494b3820c5847708b03b6b80e92bdd2", "", false),
+    player("4494b3820c5847708b03b6b80e92bdd2", "Nils", false),
+    player("", "Kyle", true),
+    player("", "Kyle", true),
+    player("", "", true)
+  ].into_iter().collect();
+
+  assert_eq!(players.len(), 3);
+}
+
+#[test]
+fn infers_the_owner_of_the_fixture() {
+  let mut parser = replay_reader::parser::Parser::from_bytes(std::fs::read("1.replay").unwrap());
+  parser.parse().unwrap();
+  let owner = PlayerId::new("4494b3820c5847708b03b6b80e92bdd2");
+
+  assert_eq!(parser.owner_id(), Some(owner.clone()));
+
+  let summary = parser.player_summary(&owner);
+  assert_eq!(summary.kills, 6);
+  assert_eq!(summary.deaths, 2);
+  assert_eq!(summary.assists, Some(parser.match_stats.as_ref().unwrap().assists));
+
+  assert_eq!(parser.finish().unwrap().owner_id(), Some(owner));
+}
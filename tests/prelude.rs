@@ -0,0 +1,30 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::prelude::*;
+
+#[test]
+fn the_prelude_covers_a_full_parse() {
+  let replay: Replay = Parser::from_bytes(std::fs::read("1.replay").unwrap()).finish().unwrap();
+
+  let meta: &Meta = &replay.meta;
+  let header: &Header = &replay.header;
+  let first: &Elimination = &replay.eliminations[0];
+  let players: &[Player] = &replay.players;
+  let stats: Option<&MatchStats> = replay.match_stats.as_ref();
+  let team_stats: Option<&TeamMatchStats> = replay.team_match_stats.as_ref();
+
+  assert_eq!(meta.name, "Unsaved Replay");
+  assert_eq!(header.version.major, 13);
+  assert_ne!(first.death_cause, DeathCause::Unknown(255));
+  assert_eq!(players.len(), 97);
+  assert!(stats.is_some() && team_stats.is_some());
+}
+
+#[test]
+fn the_prelude_covers_the_builder_and_reader() {
+  let parser: Result<Parser, ParseError> = ParserBuilder::new().build_from_bytes(vec![]);
+  let mut reader = Reader::from_bytes(vec![1]);
+
+  assert!(parser.is_ok());
+  assert!(matches!(reader.read_u32(), Err(ReaderError::OutOfBounds { .. })));
+}
@@ -0,0 +1,213 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::reader::{Reader, ReaderError};
+
+#[test]
+fn owned_bytes_outlive_later_reads() {
+  let mut reader = Reader::from_bytes(vec![1, 2, 3, 4, 5, 6]);
+
+  let first = reader.read_bytes_owned(&2).unwrap();
+  let second = reader.read_bytes_owned(&3).unwrap();
+
+  assert_eq!(first, vec![1, 2]);
+  assert_eq!(second, vec![3, 4, 5]);
+  assert_eq!(reader.offset, 5);
+}
+
+#[test]
+fn owned_bytes_are_bounds_checked() {
+  let mut reader = Reader::from_bytes(vec![1, 2, 3]);
+
+  assert!(matches!(reader.read_bytes_owned(&4), Err(ReaderError::OutOfBounds { .. })));
+  assert_eq!(reader.offset, 0);
+}
+
+#[test]
+fn aligns_to_the_next_multiple() {
+  let mut reader = Reader::from_bytes(vec![0; 16]);
+
+  reader.align_to(&4).unwrap();
+  assert_eq!(reader.offset, 0);
+
+  reader.skip(&1).unwrap();
+  reader.align_to(&4).unwrap();
+  assert_eq!(reader.offset, 4);
+
+  reader.skip(&3).unwrap();
+  reader.align_to(&8).unwrap();
+  assert_eq!(reader.offset, 8);
+
+  reader.align_to(&0).unwrap();
+  reader.align_to(&1).unwrap();
+  assert_eq!(reader.offset, 8);
+}
+
+#[test]
+fn aligning_past_the_end_fails() {
+  let mut reader = Reader::from_bytes(vec![0; 6]);
+  reader.skip(&5).unwrap();
+
+  assert!(matches!(reader.align_to(&8), Err(ReaderError::OutOfBounds { .. })));
+  assert_eq!(reader.offset, 5);
+}
+
+fn id_array(ids: &[[u8; 16]]) -> Vec<u8> {
+  let mut buffer = (ids.len() as u32).to_le_bytes().to_vec();
+  for id in ids {
+    buffer.extend_from_slice(id);
+  }
+
+  return buffer;
+}
+
+#[test]
+fn reads_arrays_of_ids() {
+  let ids = [[0x44; 16], [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0, 0, 0, 0, 0, 0, 0, 0xff]];
+  let mut reader = Reader::from_bytes(id_array(&ids));
+
+  assert_eq!(reader.read_id_vec().unwrap(), vec!["44444444444444444444444444444444", "0123456789abcdef00000000000000ff"]);
+  assert!(reader.is_eof());
+
+  reader.goto(&0).unwrap();
+  assert_eq!(reader.read_guid_vec().unwrap(), ids.to_vec());
+}
+
+#[test]
+fn id_arrays_longer_than_the_buffer_fail() {
+  let mut buffer = id_array(&[[1; 16], [2; 16]]);
+  buffer.truncate(30);
+  let mut reader = Reader::from_bytes(buffer);
+
+  assert!(matches!(reader.read_id_vec(), Err(ReaderError::OutOfBounds { .. })));
+  assert!(Reader::from_bytes(id_array(&[])).read_id_vec().unwrap().is_empty());
+}
+
+#[test]
+fn f32_round_trips() {
+  let mut buffer = 0.2926136f32.to_le_bytes().to_vec();
+  buffer.extend_from_slice(&(-1234.5f32).to_le_bytes());
+  let mut reader = Reader::from_bytes(buffer);
+
+  assert_eq!(reader.read_f32().unwrap(), 0.2926136);
+  assert_eq!(reader.offset, 4);
+  assert_eq!(reader.read_f32().unwrap(), -1234.5);
+  assert!(reader.is_eof());
+}
+
+#[test]
+fn f32_at_the_end_of_the_buffer() {
+  let mut buffer = vec![0xAA; 3];
+  buffer.extend_from_slice(&7.25f32.to_le_bytes());
+  let mut reader = Reader::from_bytes(buffer);
+  reader.skip(&3).unwrap();
+
+  assert_eq!(reader.read_f32().unwrap(), 7.25);
+  assert!(matches!(reader.read_f32(), Err(ReaderError::OutOfBounds { .. })));
+
+  let mut short = Reader::from_bytes(vec![0; 3]);
+  assert!(matches!(short.read_f32(), Err(ReaderError::OutOfBounds { .. })));
+  assert_eq!(short.offset, 0);
+}
+
+#[test]
+fn reads_signed_and_unsigned_bytes() {
+  let mut reader = Reader::from_bytes(vec![0xFF, 0x80, 0x7F, 0xFF]);
+
+  assert_eq!(reader.read_i8().unwrap(), -1);
+  assert_eq!(reader.read_i8().unwrap(), -128);
+  assert_eq!(reader.read_i8().unwrap(), 127);
+  assert_eq!(reader.offset, 3);
+  assert_eq!(reader.read_u8().unwrap(), 255);
+  assert!(reader.is_eof());
+
+  assert!(matches!(reader.read_u8(), Err(ReaderError::OutOfBounds { .. })));
+  assert!(matches!(reader.read_i8(), Err(ReaderError::OutOfBounds { .. })));
+}
+
+#[test]
+fn f64_round_trips() {
+  let mut reader = Reader::from_bytes(std::f64::consts::PI.to_le_bytes().to_vec());
+
+  assert_eq!(reader.read_f64().unwrap(), std::f64::consts::PI);
+  assert_eq!(reader.offset, 8);
+  assert!(matches!(reader.read_f64(), Err(ReaderError::OutOfBounds { .. })));
+
+  let mut short = Reader::from_bytes(vec![0; 7]);
+  assert!(matches!(short.read_f64(), Err(ReaderError::OutOfBounds { .. })));
+  assert_eq!(short.offset, 0);
+}
+
+#[test]
+fn peeking_does_not_move_the_offset() {
+  let mut reader = Reader::from_bytes(vec![0x78, 0x56, 0x34, 0x12, 0xAB, 0xCD]);
+
+  assert_eq!(reader.peek_u32().unwrap(), 0x12345678);
+  assert_eq!(reader.peek_byte().unwrap(), 0x78);
+  assert_eq!(reader.peek_bytes(&6).unwrap(), [0x78, 0x56, 0x34, 0x12, 0xAB, 0xCD]);
+  assert_eq!(reader.offset, 0);
+
+  assert_eq!(reader.read_u32().unwrap(), 0x12345678);
+  assert_eq!(reader.offset, 4);
+  assert_eq!(reader.peek_byte().unwrap(), 0xAB);
+  assert_eq!(reader.read_bytes(&2).unwrap(), [0xAB, 0xCD]);
+}
+
+#[test]
+fn peeking_past_the_end_fails() {
+  let mut reader = Reader::from_bytes(vec![1, 2, 3]);
+
+  assert!(matches!(reader.peek_u32(), Err(ReaderError::OutOfBounds { .. })));
+  assert!(matches!(reader.peek_bytes(&4), Err(ReaderError::OutOfBounds { .. })));
+  assert_eq!(reader.offset, 0);
+}
+
+#[test]
+fn any_nonzero_bool_is_true() {
+  let mut buffer = vec![];
+  for value in [0u32, 1, 0xFFFFFFFF, 2] {
+    buffer.extend_from_slice(&value.to_le_bytes());
+  }
+  let mut reader = Reader::from_bytes(buffer);
+
+  assert!(!reader.read_bool().unwrap());
+  assert!(reader.read_bool().unwrap());
+  assert!(reader.read_bool().unwrap());
+  assert!(reader.read_bool().unwrap());
+  assert!(reader.is_eof());
+}
+
+#[test]
+fn reads_single_byte_bools() {
+  let mut reader = Reader::from_bytes(vec![0, 1, 0xFF]);
+
+  assert!(!reader.read_bool_byte().unwrap());
+  assert!(reader.read_bool_byte().unwrap());
+  assert!(reader.read_bool_byte().unwrap());
+  assert!(reader.is_eof());
+}
+
+#[test]
+fn reads_nested_arrays() {
+  // [[1, 2], [], [3]] as u32 arrays of u32s
+  let mut buffer = vec![];
+  for value in [3u32, 2, 1, 2, 0, 1, 3] {
+    buffer.extend_from_slice(&value.to_le_bytes());
+  }
+  let mut reader = Reader::from_bytes(buffer);
+
+  let arrays = reader.read_array(|reader| reader.read_array(|reader| reader.read_u32())).unwrap();
+
+  assert_eq!(arrays, vec![vec![1, 2], vec![], vec![3]]);
+  assert!(reader.is_eof());
+}
+
+#[test]
+fn nested_arrays_fail_on_a_truncated_inner_array() {
+  let mut buffer = vec![];
+  for value in [2u32, 1, 5, 3, 6] {
+    buffer.extend_from_slice(&value.to_le_bytes());
+  }
+  let mut reader = Reader::from_bytes(buffer);
+
+  assert!(matches!(reader.read_array(|reader| reader.read_array(|reader| reader.read_u32())), Err(ReaderError::OutOfBounds { .. })));
+}
@@ -0,0 +1,66 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::parser::{ParseError, Parser, TimelineEvent};
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/1.replay");
+
+#[test]
+fn finish_parses_and_hands_over_the_results() {
+  let replay = Parser::from_bytes(std::fs::read(FIXTURE).unwrap()).finish().unwrap();
+
+  assert_eq!(replay.meta.name, "Unsaved Replay");
+  assert_eq!(replay.header.version.major, 13);
+  assert_eq!(replay.team_match_stats.unwrap().placement, 1);
+  assert_eq!(replay.eliminations.len(), 188);
+  assert_eq!(replay.players.len(), 97);
+  assert_eq!(replay.chunks[1].offset, 756);
+  assert_eq!(replay.checkpoints.len(), 18);
+}
+
+#[test]
+fn finish_keeps_results_that_were_already_parsed() {
+  let mut parser = Parser::from_bytes(std::fs::read(FIXTURE).unwrap());
+  parser.parse().unwrap();
+  let chunk_count = parser.chunks.len();
+
+  let replay = parser.finish().unwrap();
+
+  assert_eq!(replay.chunks.len(), chunk_count);
+}
+
+#[test]
+fn finish_needs_a_header() {
+  let buffer = std::fs::read(FIXTURE).unwrap();
+  let mut parser = Parser::from_bytes(buffer[..700].to_vec());
+  parser.parse_available().unwrap();
+
+  assert!(matches!(parser.finish(), Err(ParseError::MissingHeader)));
+}
+
+#[test]
+fn the_changelist_comes_from_the_header() {
+  let mut replay = Parser::from_bytes(std::fs::read(FIXTURE).unwrap()).finish().unwrap();
+
+  assert_eq!(replay.changelist(), 14036559);
+  assert_eq!(replay.meta.changelist, replay.header.version.changelist);
+
+  replay.meta.changelist = 1;
+  assert_eq!(replay.changelist(), 14036559);
+}
+
+#[test]
+fn timeline_is_sorted_by_timestamp_then_file_order() {
+  let mut replay = Parser::from_bytes(std::fs::read(FIXTURE).unwrap()).finish().unwrap();
+  replay.eliminations.reverse();
+  let tied_time = replay.eliminations[0].timestamp;
+  replay.eliminations[1].timestamp = tied_time;
+
+  let timeline = replay.timeline();
+  assert_eq!(timeline.len(), 188);
+  assert!(timeline.windows(2).all(|pair| pair[0].timestamp() <= pair[1].timestamp()));
+  assert!(timeline.iter().all(|event| matches!(event, TimelineEvent::Elimination(_))));
+
+  let tied: Vec<_> = timeline.iter().filter(|event| event.timestamp() == tied_time).map(|event| event.location().offset).collect();
+  assert_eq!(tied.len(), 2);
+  assert!(tied[0] < tied[1]);
+}
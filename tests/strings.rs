@@ -0,0 +1,84 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::reader::Reader;
+
+#[test]
+fn fixed_strings_end_at_the_first_null() {
+  let mut reader = Reader::from_bytes(b"Athena\0\0junk".to_vec());
+
+  assert_eq!(reader.read_string_fixed(&8).unwrap(), "Athena");
+  assert_eq!(reader.offset, 8);
+}
+
+#[test]
+fn fixed_strings_without_a_null_use_every_byte() {
+  let mut reader = Reader::from_bytes(b"Apollo".to_vec());
+
+  assert_eq!(reader.read_string_fixed(&6).unwrap(), "Apollo");
+  assert!(reader.is_eof());
+}
+
+#[test]
+fn fixed_strings_are_bounds_checked() {
+  let mut reader = Reader::from_bytes(b"Apollo".to_vec());
+
+  assert!(reader.read_string_fixed(&7).is_err());
+  assert_eq!(reader.offset, 0);
+}
+
+#[test]
+fn cstrings_stop_at_the_null() {
+  let mut reader = Reader::from_bytes(b"SubGame\0Athena\0".to_vec());
+
+  assert_eq!(reader.read_cstring().unwrap(), "SubGame");
+  assert_eq!(reader.offset, 8);
+  assert_eq!(reader.read_cstring().unwrap(), "Athena");
+  assert!(reader.is_eof());
+}
+
+#[test]
+fn empty_cstrings_only_consume_the_null() {
+  let mut reader = Reader::from_bytes(vec![0, 1]);
+
+  assert_eq!(reader.read_cstring().unwrap(), "");
+  assert_eq!(reader.offset, 1);
+}
+
+#[test]
+fn cstrings_without_a_null_are_an_error() {
+  let mut reader = Reader::from_bytes(b"Athena".to_vec());
+
+  assert!(reader.read_cstring().is_err());
+  assert_eq!(reader.offset, 0);
+}
+
+#[test]
+fn invalid_utf8_depends_on_lossy_strings() {
+  let bytes = vec![b'a', 0xFF, b'b', 0];
+
+  assert!(Reader::from_bytes(bytes.clone()).read_cstring().is_err());
+
+  let mut reader = Reader::from_bytes(bytes);
+  reader.lossy_strings = true;
+  assert_eq!(reader.read_cstring().unwrap(), "a\u{FFFD}b");
+}
+
+#[test]
+fn string_lengths_past_the_buffer_are_an_error() {
+  for length in [i32::MAX, i32::MIN, 5] {
+    let mut buffer = length.to_le_bytes().to_vec();
+    buffer.extend_from_slice(b"abc\0");
+    let mut reader = Reader::from_bytes(buffer);
+
+    assert!(reader.read_string().is_err(), "length {}", length);
+  }
+}
+
+#[test]
+fn array_lengths_past_the_buffer_are_an_error() {
+  let mut buffer = u32::MAX.to_le_bytes().to_vec();
+  buffer.extend_from_slice(&[0; 8]);
+  let mut reader = Reader::from_bytes(buffer);
+
+  assert!(reader.read_string_vec().is_err());
+}
@@ -0,0 +1,29 @@
+#![allow(clippy::needless_return)]
+
+use replay_reader::weapons::{DeathCause, ParseDeathCauseError};
+
+#[test]
+fn every_byte_round_trips_through_display() {
+  for byte in 0..=u8::MAX {
+    let death_cause = DeathCause::from_byte(byte);
+
+    assert_eq!(death_cause.to_byte(), byte);
+    assert_eq!(death_cause.to_string().parse::<DeathCause>(), Ok(death_cause));
+  }
+}
+
+#[test]
+fn displays_names_and_unknown_bytes() {
+  assert_eq!(DeathCause::Rifle.to_string(), "Rifle");
+  assert_eq!(DeathCause::DbnoTimeout.to_string(), "DbnoTimeout");
+  assert_eq!(DeathCause::Unknown(50).to_string(), "50");
+}
+
+#[test]
+fn parses_names_and_bytes() {
+  assert_eq!("Shotgun".parse(), Ok(DeathCause::Shotgun));
+  assert_eq!("3".parse(), Ok(DeathCause::Shotgun));
+  assert_eq!("50".parse(), Ok(DeathCause::Unknown(50)));
+  assert_eq!("shotgun".parse::<DeathCause>(), Err(ParseDeathCauseError(String::from("shotgun"))));
+  assert!("256".parse::<DeathCause>().is_err());
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "zlib")]
+#![allow(clippy::needless_return)]
+
+mod common;
+
+use common::ReplayFixture;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use replay_reader::compression::ZlibDecompressor;
+use replay_reader::parser::{ParseError, Parser};
+use std::io::Write;
+
+// [decompressed size: i32][compressed size: i32][zlib bytes], like decompress_buffer reads it
+fn compressed(data: &[u8], decompressed_size: i32) -> Vec<u8> {
+  let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+  encoder.write_all(data).unwrap();
+  let zlib_bytes = encoder.finish().unwrap();
+
+  let mut buffer = decompressed_size.to_le_bytes().to_vec();
+  buffer.extend_from_slice(&(zlib_bytes.len() as i32).to_le_bytes());
+  buffer.extend_from_slice(&zlib_bytes);
+
+  return buffer;
+}
+
+fn replay_data() -> Vec<u8> {
+  return (0..600u32).map(|i| (i % 7) as u8).collect();
+}
+
+fn checkpoint_data() -> Vec<u8> {
+  return b"checkpoint state ".repeat(20);
+}
+
+fn fixture(encrypted: bool, data_size: i32, checkpoint_size: i32) -> ReplayFixture {
+  let mut fixture = ReplayFixture::new(6, encrypted, "++Fortnite+Release-13.40");
+  fixture.compressed = true;
+  fixture.data_chunk = Some(compressed(&replay_data(), data_size));
+  fixture.checkpoint = Some(compressed(&checkpoint_data(), checkpoint_size));
+  fixture.team_stats = Some((1, 100));
+
+  return fixture;
+}
+
+fn parser(fixture: &ReplayFixture) -> Parser {
+  let mut parser = Parser::from_bytes(fixture.build());
+  parser.set_decompressor(ZlibDecompressor);
+
+  return parser;
+}
+
+#[test]
+fn decompresses_replay_data_and_checkpoints() {
+  for encrypted in [false, true] {
+    let fixture = fixture(encrypted, replay_data().len() as i32, checkpoint_data().len() as i32);
+    let mut parser = parser(&fixture);
+    parser.parse().unwrap();
+
+    let header = parser.header.as_ref().unwrap();
+    assert_eq!(header.version.branch, "++Fortnite+Release-13.40");
+    assert!(parser.meta.as_ref().unwrap().is_compressed);
+    assert_eq!(parser.replay_data.len(), 1);
+    assert_eq!(parser.replay_data[0].buffer, replay_data());
+    assert_eq!(parser.checkpoints.len(), 1);
+    assert_eq!(parser.checkpoints[0].buffer, checkpoint_data());
+    assert!(!parser.checkpoints[0].is_compressed);
+    assert!(parser.skipped_chunks.is_empty());
+    assert_eq!(parser.team_match_stats.as_ref().unwrap().placement, 1);
+  }
+}
+
+#[test]
+fn a_wrong_decompressed_size_is_an_error() {
+  let data_size = replay_data().len() as i32;
+  let checkpoint_size = checkpoint_data().len() as i32;
+
+  for (data_size, checkpoint_size) in [(data_size + 1, checkpoint_size), (data_size, checkpoint_size - 1), (data_size * 1000, checkpoint_size)] {
+    let mut parser = parser(&fixture(false, data_size, checkpoint_size));
+
+    assert!(matches!(parser.parse(), Err(ParseError::Decompress(_))), "sizes {} and {}", data_size, checkpoint_size);
+    assert!(parser.header.is_some());
+  }
+}
+
+#[test]
+fn a_negative_size_is_an_error() {
+  let mut parser = parser(&fixture(false, -1, checkpoint_data().len() as i32));
+
+  assert!(matches!(parser.parse(), Err(ParseError::Decompress(_))));
+}